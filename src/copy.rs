@@ -1,11 +1,15 @@
 use std::fs;
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use pyo3::prelude::*;
 
 use crate::errors::FsError;
+use crate::hash::{hash_file_internal, Algorithm};
 
 /// Progress information for a copy/move operation.
 #[pyclass(frozen)]
@@ -25,6 +29,9 @@ pub struct CopyProgress {
     pub total_files: usize,
     #[pyo3(get)]
     pub current_file: String,
+    /// Hex digest of the just-verified file, set only when `verify=True`.
+    #[pyo3(get)]
+    pub checksum: Option<String>,
 }
 
 #[pymethods]
@@ -42,18 +49,51 @@ impl CopyProgress {
     }
 }
 
+/// How aggressively to attempt copy-on-write (reflink) clones before falling
+/// back to a byte-for-byte copy.
+#[derive(Clone, Copy, PartialEq)]
+enum ReflinkMode {
+    Never,
+    Auto,
+    Always,
+}
+
+impl ReflinkMode {
+    fn parse(s: &str) -> Result<Self, FsError> {
+        match s {
+            "never" => Ok(Self::Never),
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            other => Err(FsError::Copy(format!(
+                "invalid reflink mode: {:?} (expected \"auto\", \"always\", or \"never\")",
+                other
+            ))),
+        }
+    }
+}
+
 /// Copy files/directories to a destination.
 #[pyfunction]
-#[pyo3(signature = (sources, destination, *, overwrite=false, preserve_metadata=true, progress_callback=None, callback_interval_ms=100))]
+#[pyo3(signature = (sources, destination, *, overwrite=false, preserve_metadata=true, preserve_hardlinks=false, reflink="auto", verify=false, max_workers=None, progress_callback=None, callback_interval_ms=100))]
+#[allow(clippy::too_many_arguments)]
 pub fn copy_files(
     py: Python<'_>,
     sources: Vec<String>,
     destination: &str,
     overwrite: bool,
     preserve_metadata: bool,
+    preserve_hardlinks: bool,
+    reflink: &str,
+    verify: bool,
+    max_workers: Option<usize>,
     progress_callback: Option<PyObject>,
     callback_interval_ms: u64,
 ) -> PyResult<Vec<String>> {
+    let reflink = ReflinkMode::parse(reflink)?;
+    let workers = match max_workers {
+        Some(n) => n.max(1),
+        None => thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+    };
     let dst_path = PathBuf::from(destination);
     let src_paths: Vec<PathBuf> = sources.iter().map(PathBuf::from).collect();
 
@@ -81,9 +121,34 @@ pub fn copy_files(
     }
 
     let total_files = all_operations.len();
+
+    // Fan the batch out across a worker pool (one worker per CPU unless
+    // `max_workers` says otherwise); a single worker takes the sequential
+    // path below.
+    if workers > 1 {
+        return copy_parallel(
+            py,
+            all_operations,
+            total_bytes,
+            destination,
+            overwrite,
+            preserve_metadata,
+            preserve_hardlinks,
+            reflink,
+            verify,
+            workers,
+            progress_callback,
+            callback_interval_ms,
+        );
+    }
+
     let mut result_paths = Vec::with_capacity(total_files);
     let mut bytes_copied_total: u64 = 0;
     let mut files_completed: usize = 0;
+    // Maps a source file's (device, inode) to the first destination we wrote it
+    // to, so later hardlinks to the same inode are recreated rather than copied.
+    let mut hardlink_map: std::collections::HashMap<(u64, u64), PathBuf> =
+        std::collections::HashMap::new();
 
     for (src, dst, size) in &all_operations {
         // Check for Ctrl+C
@@ -103,6 +168,31 @@ pub fn copy_files(
             fs::create_dir_all(parent)?;
         }
 
+        // Recreate hardlink structure instead of copying bytes twice.
+        if preserve_hardlinks {
+            if let Some(key) = inode_key(src) {
+                match hardlink_map.get(&key) {
+                    Some(first_dst) => {
+                        if dst.exists() {
+                            fs::remove_file(dst)?;
+                        }
+                        fs::hard_link(first_dst, dst).map_err(|e| {
+                            FsError::Copy(format!(
+                                "failed to hardlink {:?} -> {:?}: {}",
+                                dst, first_dst, e
+                            ))
+                        })?;
+                        files_completed += 1;
+                        result_paths.push(dst.to_string_lossy().into_owned());
+                        continue;
+                    }
+                    None => {
+                        hardlink_map.insert(key, dst.clone());
+                    }
+                }
+            }
+        }
+
         // Copy with progress
         let bytes = copy_single_file(
             py,
@@ -113,6 +203,7 @@ pub fn copy_files(
             total_bytes,
             files_completed,
             total_files,
+            reflink,
             progress_callback.as_ref(),
             callback_interval_ms,
         )?;
@@ -126,6 +217,29 @@ pub fn copy_files(
             }
         }
 
+        // End-to-end integrity check: re-hash source and destination.
+        if verify {
+            let checksum = verify_copy(src, dst)?;
+            if let Some(ref cb) = progress_callback {
+                let progress = CopyProgress {
+                    src: src.to_string_lossy().into_owned(),
+                    dst: dst.to_string_lossy().into_owned(),
+                    bytes_copied: bytes_copied_total,
+                    total_bytes,
+                    files_completed,
+                    total_files,
+                    current_file: src
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .into_owned(),
+                    checksum: Some(checksum),
+                };
+                let py_progress = Py::new(py, progress)?;
+                cb.call1(py, (py_progress,))?;
+            }
+        }
+
         result_paths.push(dst.to_string_lossy().into_owned());
     }
 
@@ -139,6 +253,7 @@ pub fn copy_files(
             files_completed,
             total_files,
             current_file: String::new(),
+            checksum: None,
         };
         let py_progress = Py::new(py, progress)?;
         cb.call1(py, (py_progress,))?;
@@ -199,6 +314,10 @@ pub fn move_files(
                         &dest_file.to_string_lossy(),
                         overwrite,
                         true,
+                        false,
+                        "auto",
+                        false,
+                        Some(1),
                         cb_clone,
                         callback_interval_ms,
                     )?;
@@ -225,6 +344,259 @@ pub fn move_files(
     Ok(result_paths)
 }
 
+/// Shared state for the parallel copy engine. Workers pull operation indices
+/// from `next`, update the aggregate counters, and cooperatively abort via
+/// `cancel` when any of them records the first `error`.
+struct ParallelState {
+    ops: Vec<(PathBuf, PathBuf, u64)>,
+    next: AtomicUsize,
+    bytes: AtomicU64,
+    files: AtomicUsize,
+    cancel: AtomicBool,
+    error: Mutex<Option<FsError>>,
+    /// Most recently completed file, so aggregate progress can still carry
+    /// per-file context (and the verify checksum) on the parallel path.
+    last: Mutex<LastFile>,
+    reflink: ReflinkMode,
+    preserve_metadata: bool,
+    verify: bool,
+}
+
+/// The last file a worker finished, surfaced through `emit_batch_progress`.
+#[derive(Default)]
+struct LastFile {
+    src: String,
+    dst: String,
+    checksum: Option<String>,
+}
+
+fn record_error(state: &ParallelState, err: FsError) {
+    let mut slot = state.error.lock().unwrap();
+    if slot.is_none() {
+        *slot = Some(err);
+    }
+    state.cancel.store(true, AtomicOrdering::Relaxed);
+}
+
+/// Parallel multi-file copy: dispatch independent file operations across a
+/// bounded worker pool while a single coordinator (this thread, under the GIL)
+/// drives the Python progress callback and honours Ctrl+C.
+#[allow(clippy::too_many_arguments)]
+fn copy_parallel(
+    py: Python<'_>,
+    all_operations: Vec<(PathBuf, PathBuf, u64)>,
+    total_bytes: u64,
+    destination: &str,
+    overwrite: bool,
+    preserve_metadata: bool,
+    preserve_hardlinks: bool,
+    reflink: ReflinkMode,
+    verify: bool,
+    workers: usize,
+    progress_callback: Option<PyObject>,
+    callback_interval_ms: u64,
+) -> PyResult<Vec<String>> {
+    let total_files = all_operations.len();
+    let ordered_dsts: Vec<String> = all_operations
+        .iter()
+        .map(|(_, dst, _)| dst.to_string_lossy().into_owned())
+        .collect();
+
+    // Sequential pre-pass: validate overwrite, create parent directories, and
+    // split hardlinked duplicates out so their bytes are only copied once. This
+    // keeps the ordering guarantees the sequential path provides.
+    let mut copy_ops: Vec<(PathBuf, PathBuf, u64)> = Vec::new();
+    let mut link_ops: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut hardlink_map: std::collections::HashMap<(u64, u64), PathBuf> =
+        std::collections::HashMap::new();
+
+    for (src, dst, size) in all_operations {
+        if dst.exists() && !overwrite {
+            return Err(FsError::Copy(format!(
+                "destination already exists: {:?} (use overwrite=True)",
+                dst
+            ))
+            .into());
+        }
+        if let Some(parent) = dst.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if preserve_hardlinks {
+            if let Some(key) = inode_key(&src) {
+                match hardlink_map.get(&key) {
+                    Some(first_dst) => {
+                        link_ops.push((first_dst.clone(), dst));
+                        continue;
+                    }
+                    None => {
+                        hardlink_map.insert(key, dst.clone());
+                    }
+                }
+            }
+        }
+        copy_ops.push((src, dst, size));
+    }
+
+    let state = Arc::new(ParallelState {
+        ops: copy_ops,
+        next: AtomicUsize::new(0),
+        bytes: AtomicU64::new(0),
+        files: AtomicUsize::new(0),
+        cancel: AtomicBool::new(false),
+        error: Mutex::new(None),
+        last: Mutex::new(LastFile::default()),
+        reflink,
+        preserve_metadata,
+        verify,
+    });
+
+    let op_count = state.ops.len();
+    let worker_count = workers.min(op_count.max(1));
+    let mut handles = Vec::with_capacity(worker_count);
+    for _ in 0..worker_count {
+        let st = Arc::clone(&state);
+        handles.push(thread::spawn(move || {
+            loop {
+                if st.cancel.load(AtomicOrdering::Relaxed) {
+                    break;
+                }
+                let i = st.next.fetch_add(1, AtomicOrdering::Relaxed);
+                if i >= st.ops.len() {
+                    break;
+                }
+                let (src, dst, size) = &st.ops[i];
+                match copy_file_raw(src, dst, st.reflink, &st.cancel) {
+                    Ok(()) => {
+                        if st.preserve_metadata {
+                            if let Ok(m) = fs::metadata(src) {
+                                let _ = fs::set_permissions(dst, m.permissions());
+                            }
+                        }
+                        let checksum = if st.verify {
+                            match verify_copy(src, dst) {
+                                Ok(c) => Some(c),
+                                Err(e) => {
+                                    record_error(&st, e);
+                                    break;
+                                }
+                            }
+                        } else {
+                            None
+                        };
+                        st.bytes.fetch_add(*size, AtomicOrdering::Relaxed);
+                        st.files.fetch_add(1, AtomicOrdering::Relaxed);
+                        if let Ok(mut last) = st.last.lock() {
+                            *last = LastFile {
+                                src: src.to_string_lossy().into_owned(),
+                                dst: dst.to_string_lossy().into_owned(),
+                                checksum,
+                            };
+                        }
+                    }
+                    Err(e) => {
+                        record_error(&st, e);
+                        break;
+                    }
+                }
+            }
+        }));
+    }
+
+    // Coordinator: serialize callbacks under the GIL and watch for Ctrl+C.
+    let interval = Duration::from_millis(callback_interval_ms.max(1));
+    loop {
+        if state.cancel.load(AtomicOrdering::Relaxed)
+            || state.next.load(AtomicOrdering::Relaxed) >= op_count
+        {
+            break;
+        }
+        py.allow_threads(|| thread::sleep(interval));
+        if let Err(e) = py.check_signals() {
+            state.cancel.store(true, AtomicOrdering::Relaxed);
+            for h in handles {
+                let _ = h.join();
+            }
+            return Err(e);
+        }
+        if let Some(ref cb) = progress_callback {
+            emit_batch_progress(py, &state, cb, destination, total_bytes, total_files)?;
+        }
+    }
+
+    for h in handles {
+        let _ = h.join();
+    }
+
+    if let Some(err) = state.error.lock().unwrap().take() {
+        return Err(err.into());
+    }
+
+    // Recreate the hardlink structure now that all first copies are on disk.
+    for (first_dst, dst) in link_ops {
+        if dst.exists() {
+            fs::remove_file(&dst)?;
+        }
+        fs::hard_link(&first_dst, &dst).map_err(|e| {
+            FsError::Copy(format!(
+                "failed to hardlink {:?} -> {:?}: {}",
+                dst, first_dst, e
+            ))
+        })?;
+    }
+
+    // Final aggregate callback.
+    if let Some(ref cb) = progress_callback {
+        let progress = CopyProgress {
+            src: String::new(),
+            dst: destination.to_string(),
+            bytes_copied: total_bytes,
+            total_bytes,
+            files_completed: total_files,
+            total_files,
+            current_file: String::new(),
+            checksum: None,
+        };
+        let py_progress = Py::new(py, progress)?;
+        cb.call1(py, (py_progress,))?;
+    }
+
+    Ok(ordered_dsts)
+}
+
+fn emit_batch_progress(
+    py: Python<'_>,
+    state: &ParallelState,
+    callback: &PyObject,
+    destination: &str,
+    total_bytes: u64,
+    total_files: usize,
+) -> PyResult<()> {
+    let last = state.last.lock().unwrap();
+    let current_file = Path::new(&last.src)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let progress = CopyProgress {
+        src: last.src.clone(),
+        dst: if last.dst.is_empty() {
+            destination.to_string()
+        } else {
+            last.dst.clone()
+        },
+        bytes_copied: state.bytes.load(AtomicOrdering::Relaxed),
+        total_bytes,
+        files_completed: state.files.load(AtomicOrdering::Relaxed),
+        total_files,
+        current_file,
+        checksum: last.checksum.clone(),
+    };
+    drop(last);
+    let py_progress = Py::new(py, progress)?;
+    callback.call1(py, (py_progress,))?;
+    Ok(())
+}
+
 fn collect_dir_operations(
     src_dir: &Path,
     dst_base: &Path,
@@ -256,14 +628,53 @@ fn copy_single_file(
     py: Python<'_>,
     src: &Path,
     dst: &Path,
-    _file_size: u64,
+    file_size: u64,
     bytes_copied_before: u64,
     total_bytes: u64,
     files_completed: usize,
     total_files: usize,
+    reflink: ReflinkMode,
     callback: Option<&PyObject>,
     callback_interval_ms: u64,
 ) -> PyResult<u64> {
+    // Copy-on-write fast path: share extents instead of streaming bytes.
+    if reflink != ReflinkMode::Never {
+        match try_reflink(src, dst) {
+            Ok(true) => {
+                if let Some(cb) = callback {
+                    let progress = CopyProgress {
+                        src: src.to_string_lossy().into_owned(),
+                        dst: dst.to_string_lossy().into_owned(),
+                        bytes_copied: bytes_copied_before + file_size,
+                        total_bytes,
+                        files_completed,
+                        total_files,
+                        current_file: src
+                            .file_name()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .into_owned(),
+                        checksum: None,
+                    };
+                    let py_progress = Py::new(py, progress)?;
+                    cb.call1(py, (py_progress,))?;
+                }
+                return Ok(file_size);
+            }
+            Ok(false) => {
+                if reflink == ReflinkMode::Always {
+                    return Err(FsError::Copy(format!(
+                        "reflink not supported for {:?} (reflink=\"always\")",
+                        dst
+                    ))
+                    .into());
+                }
+                // Unsupported filesystem: fall through to the byte copy.
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
     let src_file = fs::File::open(src)?;
     let dst_file = fs::File::create(dst)?;
     let mut reader = BufReader::with_capacity(256 * 1024, src_file);
@@ -300,6 +711,7 @@ fn copy_single_file(
                         .unwrap_or_default()
                         .to_string_lossy()
                         .into_owned(),
+                    checksum: None,
                 };
                 let py_progress = Py::new(py, progress)?;
                 cb.call1(py, (py_progress,))?;
@@ -312,6 +724,142 @@ fn copy_single_file(
     Ok(bytes_this_file)
 }
 
+/// GIL-free byte copy used by the parallel engine's workers. Honours the same
+/// reflink fast path as [`copy_single_file`] but emits no Python callbacks and
+/// bails promptly when `cancel` is set.
+fn copy_file_raw(
+    src: &Path,
+    dst: &Path,
+    reflink: ReflinkMode,
+    cancel: &AtomicBool,
+) -> Result<(), FsError> {
+    if reflink != ReflinkMode::Never {
+        if try_reflink(src, dst)? {
+            return Ok(());
+        }
+        if reflink == ReflinkMode::Always {
+            return Err(FsError::Copy(format!(
+                "reflink not supported for {:?} (reflink=\"always\")",
+                dst
+            )));
+        }
+    }
+
+    let src_file = fs::File::open(src)?;
+    let dst_file = fs::File::create(dst)?;
+    let mut reader = BufReader::with_capacity(256 * 1024, src_file);
+    let mut writer = BufWriter::with_capacity(256 * 1024, dst_file);
+    let mut buf = vec![0u8; 256 * 1024];
+
+    loop {
+        if cancel.load(AtomicOrdering::Relaxed) {
+            return Ok(());
+        }
+        let n = reader
+            .read(&mut buf)
+            .map_err(|e| FsError::Copy(e.to_string()))?;
+        if n == 0 {
+            break;
+        }
+        writer
+            .write_all(&buf[..n])
+            .map_err(|e| FsError::Copy(e.to_string()))?;
+    }
+    writer.flush().map_err(|e| FsError::Copy(e.to_string()))?;
+    Ok(())
+}
+
+/// Confirm that `dst` is a byte-for-byte copy of `src` by hashing both with a
+/// fast non-cryptographic digest (xxh3). On mismatch the corrupt destination is
+/// removed and an error is returned; on success the shared digest is returned.
+///
+/// [`hash_file_internal`] memory-maps files above `MMAP_THRESHOLD` and uses
+/// buffered reads below it, so this reuses the mmap fast path for large files.
+fn verify_copy(src: &Path, dst: &Path) -> Result<String, FsError> {
+    let src_hash = hash_file_internal(src, Algorithm::Xxh3, 256 * 1024)?;
+    let dst_hash = hash_file_internal(dst, Algorithm::Xxh3, 256 * 1024)?;
+    if src_hash.hash_hex != dst_hash.hash_hex {
+        let _ = fs::remove_file(dst);
+        return Err(FsError::Copy(format!(
+            "verification failed for {:?}: source {} != destination {}",
+            dst, src_hash.hash_hex, dst_hash.hash_hex
+        )));
+    }
+    Ok(dst_hash.hash_hex)
+}
+
+/// The `(device, inode)` identity of a file, used to detect hardlinked sources.
+#[cfg(unix)]
+fn inode_key(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path).ok().map(|m| (m.dev(), m.ino()))
+}
+
+#[cfg(not(unix))]
+fn inode_key(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+/// Attempt a copy-on-write clone of `src` to `dst`.
+///
+/// Returns `Ok(true)` on a successful clone, `Ok(false)` when the filesystem
+/// does not support reflinks (so the caller should fall back to a byte copy),
+/// and `Err` for any other failure.
+#[cfg(target_os = "linux")]
+fn try_reflink(src: &Path, dst: &Path) -> Result<bool, FsError> {
+    use std::os::unix::io::AsRawFd;
+
+    // FICLONE: clone an entire file's extents (from linux/fs.h).
+    const FICLONE: libc::c_ulong = 0x4004_9409;
+
+    let src_file = fs::File::open(src)?;
+    if dst.exists() {
+        fs::remove_file(dst)?;
+    }
+    let dst_file = fs::File::create(dst)?;
+    let ret = unsafe { libc::ioctl(dst_file.as_raw_fd(), FICLONE, src_file.as_raw_fd()) };
+    if ret == 0 {
+        return Ok(true);
+    }
+
+    let err = std::io::Error::last_os_error();
+    drop(dst_file);
+    let _ = fs::remove_file(dst);
+    match err.raw_os_error() {
+        Some(libc::EOPNOTSUPP) | Some(libc::EXDEV) | Some(libc::EINVAL) => Ok(false),
+        _ => Err(FsError::Copy(format!("reflink failed for {:?}: {}", dst, err))),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn try_reflink(src: &Path, dst: &Path) -> Result<bool, FsError> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    if dst.exists() {
+        fs::remove_file(dst)?;
+    }
+    let src_c =
+        CString::new(src.as_os_str().as_bytes()).map_err(|e| FsError::Copy(e.to_string()))?;
+    let dst_c =
+        CString::new(dst.as_os_str().as_bytes()).map_err(|e| FsError::Copy(e.to_string()))?;
+    let ret = unsafe { libc::clonefile(src_c.as_ptr(), dst_c.as_ptr(), 0) };
+    if ret == 0 {
+        return Ok(true);
+    }
+
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::ENOTSUP) | Some(libc::EXDEV) | Some(libc::EINVAL) => Ok(false),
+        _ => Err(FsError::Copy(format!("clonefile failed for {:?}: {}", dst, err))),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn try_reflink(_src: &Path, _dst: &Path) -> Result<bool, FsError> {
+    Ok(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -333,4 +881,57 @@ mod tests {
         assert_eq!(ops.len(), 2);
         assert_eq!(total, 6); // 3 + 3 bytes
     }
+
+    #[test]
+    fn test_reflink_mode_parse() {
+        assert!(ReflinkMode::parse("auto").is_ok());
+        assert!(ReflinkMode::parse("always").is_ok());
+        assert!(ReflinkMode::parse("never").is_ok());
+        assert!(ReflinkMode::parse("sometimes").is_err());
+    }
+
+    #[test]
+    fn test_reflink_copies_contents() {
+        // A successful clone leaves identical contents at the destination; on a
+        // filesystem without clone support no partial destination is left behind.
+        let tmp = TempDir::new().unwrap();
+        let src = tmp.path().join("a.bin");
+        let dst = tmp.path().join("b.bin");
+        fs::write(&src, b"reflink payload").unwrap();
+        match try_reflink(&src, &dst) {
+            Ok(true) => assert_eq!(fs::read(&dst).unwrap(), b"reflink payload"),
+            Ok(false) => assert!(!dst.exists()),
+            Err(e) => panic!("unexpected reflink error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_verify_copy_matches_and_detects_corruption() {
+        let tmp = TempDir::new().unwrap();
+        let src = tmp.path().join("src.bin");
+        let good = tmp.path().join("good.bin");
+        let bad = tmp.path().join("bad.bin");
+        fs::write(&src, b"integrity-check payload").unwrap();
+        fs::copy(&src, &good).unwrap();
+        fs::write(&bad, b"different contents here!").unwrap();
+
+        let digest = verify_copy(&src, &good).unwrap();
+        assert!(!digest.is_empty());
+
+        assert!(verify_copy(&src, &bad).is_err());
+        assert!(!bad.exists(), "corrupt destination should be removed");
+    }
+
+    #[test]
+    fn test_copy_file_raw_copies_bytes() {
+        let tmp = TempDir::new().unwrap();
+        let src = tmp.path().join("src.bin");
+        let dst = tmp.path().join("dst.bin");
+        let payload = vec![7u8; 300 * 1024]; // larger than one buffer
+        fs::write(&src, &payload).unwrap();
+
+        let cancel = AtomicBool::new(false);
+        copy_file_raw(&src, &dst, ReflinkMode::Never, &cancel).unwrap();
+        assert_eq!(fs::read(&dst).unwrap(), payload);
+    }
 }
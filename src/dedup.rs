@@ -1,13 +1,215 @@
-use std::collections::HashMap;
+//! Duplicate-file detection via a staged size → partial-hash → full-hash
+//! pipeline.
+//!
+//! The pipeline mirrors tools like czkawka/ddh and avoids full reads of the
+//! vast majority of files:
+//!
+//! 1. `fs::metadata` every input and bucket by `file_size`; any size mapping to
+//!    a single file is discarded, since a unique size cannot have a duplicate.
+//! 2. Within each surviving size bucket, compute [`hash::partial_hash`]
+//!    (head+tail) in parallel with rayon and sub-bucket by that partial digest,
+//!    again discarding singletons.
+//! 3. Only for the remaining candidates compute the full digest and group by it.
+//!
+//! [`find_duplicates`] returns one [`DuplicateGroup`] per surviving set of two
+//! or more byte-identical files.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use pyo3::prelude::*;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::errors::FsError;
 use crate::hash::{self, Algorithm};
 
+/// Compiled file/directory filters applied during the collect stage.
+#[derive(Default)]
+struct CollectFilter {
+    allowed_exts: Option<Vec<String>>,
+    excluded_exts: Vec<String>,
+    exclude_globs: Option<GlobSet>,
+    exclude_dirs: Vec<String>,
+}
+
+impl CollectFilter {
+    fn build(
+        allowed_extensions: Option<Vec<String>>,
+        excluded_extensions: Option<Vec<String>>,
+        exclude_globs: Option<Vec<String>>,
+        exclude_dirs: Option<Vec<String>>,
+    ) -> Result<Self, FsError> {
+        let lower = |v: Vec<String>| v.into_iter().map(|e| e.trim_start_matches('.').to_ascii_lowercase()).collect::<Vec<_>>();
+
+        let exclude_globs = match exclude_globs {
+            Some(patterns) if !patterns.is_empty() => {
+                let mut builder = GlobSetBuilder::new();
+                for p in &patterns {
+                    let glob = Glob::new(p)
+                        .map_err(|e| FsError::Hash(format!("invalid exclude glob {:?}: {}", p, e)))?;
+                    builder.add(glob);
+                }
+                Some(
+                    builder
+                        .build()
+                        .map_err(|e| FsError::Hash(format!("failed to build glob set: {}", e)))?,
+                )
+            }
+            _ => None,
+        };
+
+        Ok(CollectFilter {
+            allowed_exts: allowed_extensions.map(lower),
+            excluded_exts: excluded_extensions.map(lower).unwrap_or_default(),
+            exclude_globs,
+            exclude_dirs: exclude_dirs.unwrap_or_default(),
+        })
+    }
+
+    /// Whether a file path passes the extension and glob filters.
+    fn allows_file(&self, path: &Path) -> bool {
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase());
+
+        if let Some(ref allowed) = self.allowed_exts {
+            match &ext {
+                Some(e) if allowed.iter().any(|a| a == e) => {}
+                _ => return false,
+            }
+        }
+        if let Some(ref e) = ext {
+            if self.excluded_exts.iter().any(|x| x == e) {
+                return false;
+            }
+        }
+        if let Some(ref set) = self.exclude_globs {
+            if let Some(name) = path.file_name() {
+                if set.is_match(name) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// A cache entry for a previously hashed file, keyed by `(size, path)`.
+#[derive(Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    mtime_ns: u128,
+    #[cfg(unix)]
+    dev: u64,
+    #[cfg(unix)]
+    ino: u64,
+    algorithm: String,
+    hash_hex: String,
+}
+
+/// Persistent, size-keyed cache of full file hashes so unchanged files are not
+/// re-read across runs.
+///
+/// The on-disk format is a serde (JSON) serialization of a map keyed by
+/// `(size, path)`; each entry stores the file's mtime (plus dev/ino on unix)
+/// and the full hash hex along with the algorithm it was produced with. Only
+/// entries whose size and mtime still match the current metadata are reused.
+#[derive(Default, Serialize, Deserialize)]
+pub struct DuplicateCache {
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl DuplicateCache {
+    /// Key combining file size and path, mirroring czkawka's size-keyed cache.
+    fn key(size: u64, path: &Path) -> String {
+        format!("{}\u{0}{}", size, path.to_string_lossy())
+    }
+
+    /// Load a cache from `path`. A missing file yields an empty cache; a
+    /// corrupt file is treated as empty rather than aborting the scan.
+    pub fn load(path: &Path) -> Self {
+        match fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => DuplicateCache::default(),
+        }
+    }
+
+    /// Look up a cached hash for `path`, returning it only if the stored size,
+    /// mtime (and dev/ino on unix) still match the supplied metadata.
+    fn get(&self, size: u64, path: &Path, meta: &fs::Metadata, algo: Algorithm) -> Option<String> {
+        let entry = self.entries.get(&Self::key(size, path))?;
+        if entry.algorithm != algo.name() {
+            return None;
+        }
+        if entry.mtime_ns != mtime_ns(meta) {
+            return None;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if entry.dev != meta.dev() || entry.ino != meta.ino() {
+                return None;
+            }
+        }
+        Some(entry.hash_hex.clone())
+    }
+
+    /// Insert or refresh the cached hash for `path`.
+    fn insert(
+        &mut self,
+        size: u64,
+        path: &Path,
+        meta: &fs::Metadata,
+        algo: Algorithm,
+        hash_hex: String,
+    ) {
+        let entry = CacheEntry {
+            mtime_ns: mtime_ns(meta),
+            #[cfg(unix)]
+            dev: {
+                use std::os::unix::fs::MetadataExt;
+                meta.dev()
+            },
+            #[cfg(unix)]
+            ino: {
+                use std::os::unix::fs::MetadataExt;
+                meta.ino()
+            },
+            algorithm: algo.name().to_string(),
+            hash_hex,
+        };
+        self.entries.insert(Self::key(size, path), entry);
+    }
+
+    /// Drop entries whose paths no longer exist on disk.
+    pub fn prune(&mut self) {
+        self.entries.retain(|key, _| match key.split_once('\u{0}') {
+            Some((_, path)) => Path::new(path).exists(),
+            None => false,
+        });
+    }
+
+    /// Serialize the cache back to `path`.
+    pub fn save(&self, path: &Path) -> Result<(), FsError> {
+        let bytes = serde_json::to_vec(self)
+            .map_err(|e| FsError::Hash(format!("failed to serialize cache: {}", e)))?;
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+}
+
+fn mtime_ns(meta: &fs::Metadata) -> u128 {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
 /// A group of files that are duplicates of each other.
 #[pyclass(frozen)]
 #[derive(Clone)]
@@ -18,24 +220,35 @@ pub struct DuplicateGroup {
     pub file_size: u64,
     #[pyo3(get)]
     pub paths: Vec<String>,
+    /// Number of distinct on-disk copies (distinct inodes on unix). Paths that
+    /// are already hardlinks to the same inode count once.
+    #[pyo3(get)]
+    pub real_copies: usize,
+    /// Paths that share an inode with an earlier path in `paths` and therefore
+    /// do not consume additional storage.
+    #[pyo3(get)]
+    pub hardlinked_paths: Vec<String>,
 }
 
 #[pymethods]
 impl DuplicateGroup {
     /// Bytes wasted by keeping all copies instead of just one.
+    ///
+    /// Computed from distinct inodes only, so files that are already hardlinks
+    /// to one another are not double-counted as reclaimable.
     #[getter]
     fn wasted_bytes(&self) -> u64 {
-        if self.paths.len() <= 1 {
+        if self.real_copies <= 1 {
             return 0;
         }
-        self.file_size * (self.paths.len() as u64 - 1)
+        self.file_size * (self.real_copies as u64 - 1)
     }
 
     fn __repr__(&self) -> String {
         format!(
             "DuplicateGroup({}B x {} copies, wasted={}B)",
             self.file_size,
-            self.paths.len(),
+            self.real_copies,
             self.wasted_bytes()
         )
     }
@@ -45,6 +258,38 @@ impl DuplicateGroup {
     }
 }
 
+/// Compute the number of distinct on-disk copies and the subset of paths that
+/// are hardlinks of an earlier path.
+#[cfg(unix)]
+fn inode_breakdown(paths: &[String]) -> (usize, Vec<String>) {
+    use std::collections::HashSet;
+    use std::os::unix::fs::MetadataExt;
+
+    let mut seen: HashSet<(u64, u64)> = HashSet::new();
+    let mut hardlinked = Vec::new();
+    let mut distinct = 0;
+    for p in paths {
+        match fs::metadata(p) {
+            Ok(m) => {
+                if seen.insert((m.dev(), m.ino())) {
+                    distinct += 1;
+                } else {
+                    hardlinked.push(p.clone());
+                }
+            }
+            // If we cannot stat it, treat it as its own distinct copy.
+            Err(_) => distinct += 1,
+        }
+    }
+    (distinct, hardlinked)
+}
+
+#[cfg(not(unix))]
+fn inode_breakdown(paths: &[String]) -> (usize, Vec<String>) {
+    // No inode concept available: every path is a distinct copy.
+    (paths.len(), Vec::new())
+}
+
 /// Find duplicate files using a staged pipeline.
 ///
 /// Pipeline:
@@ -52,18 +297,31 @@ impl DuplicateGroup {
 /// 2. Partial hash (first + last `partial_hash_size` bytes)
 /// 3. Full hash only for files matching in steps 1 and 2
 #[pyfunction]
-#[pyo3(signature = (paths, *, recursive=true, min_size=1, algorithm="blake3", partial_hash_size=4096, max_workers=None, progress_callback=None))]
+#[pyo3(signature = (paths, *, recursive=true, min_size=1, max_size=None, algorithm="blake3", partial_hash_size=4096, max_workers=None, cache_path=None, allowed_extensions=None, excluded_extensions=None, exclude_globs=None, exclude_dirs=None, progress_callback=None))]
+#[allow(clippy::too_many_arguments)]
 pub fn find_duplicates(
     py: Python<'_>,
     paths: Vec<String>,
     recursive: bool,
     min_size: u64,
+    max_size: Option<u64>,
     algorithm: &str,
     partial_hash_size: usize,
     max_workers: Option<usize>,
+    cache_path: Option<String>,
+    allowed_extensions: Option<Vec<String>>,
+    excluded_extensions: Option<Vec<String>>,
+    exclude_globs: Option<Vec<String>>,
+    exclude_dirs: Option<Vec<String>>,
     progress_callback: Option<PyObject>,
 ) -> PyResult<Vec<DuplicateGroup>> {
     let algo = Algorithm::from_str(algorithm)?;
+    let filter = CollectFilter::build(
+        allowed_extensions,
+        excluded_extensions,
+        exclude_globs,
+        exclude_dirs,
+    )?;
 
     // Build optional custom thread pool
     let pool = if let Some(workers) = max_workers {
@@ -80,7 +338,8 @@ pub fn find_duplicates(
     // Stage 1: Collect files and group by size
     report_progress(py, &progress_callback, "collecting", 0, 0)?;
 
-    let file_entries = py.allow_threads(|| collect_files(&paths, recursive, min_size))?;
+    let file_entries =
+        py.allow_threads(|| collect_files(&paths, recursive, min_size, max_size, &filter))?;
     let total_files = file_entries.len();
 
     report_progress(py, &progress_callback, "size_grouping", 0, total_files)?;
@@ -118,21 +377,42 @@ pub fn find_duplicates(
     // Stage 3: Full hash
     report_progress(py, &progress_callback, "full_hash", 0, partial_count)?;
 
+    let cache_file = cache_path.map(PathBuf::from);
     let full_groups = py.allow_threads(|| {
-        let work = || full_hash_stage(&candidates_after_partial, algo);
-        match &pool {
+        // Load the persistent hash cache (if enabled) so unchanged files are
+        // not re-read across runs.
+        let mut cache = cache_file.as_deref().map(DuplicateCache::load);
+        let work = || full_hash_stage(&candidates_after_partial, algo, cache.as_mut());
+        let groups = match &pool {
             Some(p) => p.install(work),
             None => work(),
+        };
+        // Write the updated cache back, pruning vanished paths.
+        if let (Some(path), Some(mut cache)) = (cache_file.as_deref(), cache) {
+            cache.prune();
+            if let Err(e) = cache.save(path) {
+                log::warn!("failed to write hash cache: {}", e);
+            }
         }
+        groups
     });
 
     let mut duplicates: Vec<DuplicateGroup> = full_groups
         .into_iter()
         .filter(|(_, _, files)| files.len() > 1)
-        .map(|(hash_hex, size, files)| DuplicateGroup {
-            hash_hex,
-            file_size: size,
-            paths: files.into_iter().map(|p| p.to_string_lossy().into_owned()).collect(),
+        .map(|(hash_hex, size, files)| {
+            let paths: Vec<String> = files
+                .into_iter()
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect();
+            let (real_copies, hardlinked_paths) = inode_breakdown(&paths);
+            DuplicateGroup {
+                hash_hex,
+                file_size: size,
+                paths,
+                real_copies,
+                hardlinked_paths,
+            }
         })
         .collect();
 
@@ -145,6 +425,308 @@ pub fn find_duplicates(
     Ok(duplicates)
 }
 
+/// A single file reported by [`find_large_files`].
+#[pyclass(frozen)]
+#[derive(Clone)]
+pub struct LargeFile {
+    #[pyo3(get)]
+    pub path: String,
+    #[pyo3(get)]
+    pub file_size: u64,
+}
+
+#[pymethods]
+impl LargeFile {
+    fn __repr__(&self) -> String {
+        format!("LargeFile({:?}, {}B)", self.path, self.file_size)
+    }
+}
+
+/// Report the largest files under the given paths.
+///
+/// When `top_n` is set, only the `top_n` largest files are retained using a
+/// bounded min-heap; the walk still gathers every matching file into a list
+/// before the heap is filled, so peak memory grows with the number of files
+/// scanned, not with `top_n`. When it is `None`, every file at or above
+/// `min_size` is returned. Results are sorted by size descending.
+#[pyfunction]
+#[pyo3(signature = (paths, *, recursive=true, top_n=None, min_size=1))]
+pub fn find_large_files(
+    py: Python<'_>,
+    paths: Vec<String>,
+    recursive: bool,
+    top_n: Option<usize>,
+    min_size: u64,
+) -> PyResult<Vec<LargeFile>> {
+    let files = py.allow_threads(|| {
+        let entries = collect_files(&paths, recursive, min_size, None, &CollectFilter::default())?;
+        Ok::<_, FsError>(largest(entries, top_n))
+    })?;
+    Ok(files)
+}
+
+fn largest(entries: Vec<(PathBuf, u64)>, top_n: Option<usize>) -> Vec<LargeFile> {
+    let mut sorted = match top_n {
+        Some(n) if n > 0 => {
+            // Bounded min-heap: keep only the n largest seen so far.
+            let mut heap: BinaryHeap<Reverse<(u64, PathBuf)>> = BinaryHeap::with_capacity(n + 1);
+            for (path, size) in entries {
+                heap.push(Reverse((size, path)));
+                if heap.len() > n {
+                    heap.pop();
+                }
+            }
+            heap.into_iter().map(|Reverse(e)| e).collect::<Vec<_>>()
+        }
+        Some(_) => Vec::new(),
+        None => entries.into_iter().map(|(p, s)| (s, p)).collect(),
+    };
+
+    // Sort by size descending, then by path for a stable order.
+    sorted.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    sorted
+        .into_iter()
+        .map(|(size, path)| LargeFile {
+            path: path.to_string_lossy().into_owned(),
+            file_size: size,
+        })
+        .collect()
+}
+
+/// Summary returned by [`resolve_duplicates`].
+#[pyclass(frozen)]
+#[derive(Clone)]
+pub struct ResolveSummary {
+    /// Bytes reclaimed (or that would be reclaimed, for a dry run).
+    #[pyo3(get)]
+    pub bytes_reclaimed: u64,
+    /// Per-path outcome: `(path, outcome)` where outcome is one of
+    /// `"kept"`, `"deleted"`, `"hardlinked"`, `"symlinked"`, `"skipped"`, or
+    /// `"would_delete"` (every redundant copy in a dry run).
+    #[pyo3(get)]
+    pub outcomes: Vec<(String, String)>,
+}
+
+#[pymethods]
+impl ResolveSummary {
+    fn __repr__(&self) -> String {
+        format!(
+            "ResolveSummary(reclaimed={}B, {} outcomes)",
+            self.bytes_reclaimed,
+            self.outcomes.len()
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ResolveAction {
+    Delete,
+    Hardlink,
+    Symlink,
+    DryRun,
+}
+
+impl ResolveAction {
+    fn from_str(s: &str) -> Result<Self, FsError> {
+        match s {
+            "delete" => Ok(ResolveAction::Delete),
+            "hardlink" => Ok(ResolveAction::Hardlink),
+            "symlink" => Ok(ResolveAction::Symlink),
+            "dry_run" => Ok(ResolveAction::DryRun),
+            other => Err(FsError::Copy(format!(
+                "unknown action {:?}, expected \"delete\", \"hardlink\", \"symlink\" or \"dry_run\"",
+                other
+            ))),
+        }
+    }
+}
+
+/// Act on duplicate groups by removing, hardlinking or symlinking the
+/// redundant copies, keeping one file per group chosen by `keep`.
+///
+/// `action` is one of `"delete"`, `"hardlink"`, `"symlink"`, or `"dry_run"`
+/// (which only reports what would happen). `keep` is a selection policy:
+/// `"newest"`, `"oldest"`, `"shortest_path"`, or an explicit path/prefix so
+/// copies under a preferred directory survive.
+///
+/// Link replacements are crash-safe: the link is created at a temporary name
+/// in the same directory and atomically renamed over the original.
+#[pyfunction]
+#[pyo3(signature = (groups, action, *, keep="newest"))]
+pub fn resolve_duplicates(
+    py: Python<'_>,
+    groups: Vec<DuplicateGroup>,
+    action: &str,
+    keep: &str,
+) -> PyResult<ResolveSummary> {
+    let action = ResolveAction::from_str(action)?;
+    let summary = py.allow_threads(|| resolve_groups(&groups, action, keep))?;
+    Ok(summary)
+}
+
+fn resolve_groups(
+    groups: &[DuplicateGroup],
+    action: ResolveAction,
+    keep: &str,
+) -> Result<ResolveSummary, FsError> {
+    let mut bytes_reclaimed: u64 = 0;
+    let mut outcomes: Vec<(String, String)> = Vec::new();
+
+    for group in groups {
+        if group.paths.len() <= 1 {
+            continue;
+        }
+        let keep_idx = select_keeper(&group.paths, keep);
+        let keeper = PathBuf::from(&group.paths[keep_idx]);
+        outcomes.push((group.paths[keep_idx].clone(), "kept".to_string()));
+
+        for (idx, path_str) in group.paths.iter().enumerate() {
+            if idx == keep_idx {
+                continue;
+            }
+            let path = PathBuf::from(path_str);
+
+            // Skip files already sharing an inode with the keeper (no-op).
+            if shares_inode(&keeper, &path) {
+                outcomes.push((path_str.clone(), "skipped".to_string()));
+                continue;
+            }
+
+            let outcome = match action {
+                ResolveAction::DryRun => {
+                    bytes_reclaimed += group.file_size;
+                    "would_delete"
+                }
+                ResolveAction::Delete => {
+                    fs::remove_file(&path)?;
+                    bytes_reclaimed += group.file_size;
+                    "deleted"
+                }
+                ResolveAction::Hardlink => {
+                    replace_with_link(&keeper, &path, LinkKind::Hard)?;
+                    bytes_reclaimed += group.file_size;
+                    "hardlinked"
+                }
+                ResolveAction::Symlink => {
+                    replace_with_link(&keeper, &path, LinkKind::Sym)?;
+                    bytes_reclaimed += group.file_size;
+                    "symlinked"
+                }
+            };
+            outcomes.push((path_str.clone(), outcome.to_string()));
+        }
+    }
+
+    Ok(ResolveSummary {
+        bytes_reclaimed,
+        outcomes,
+    })
+}
+
+#[derive(Clone, Copy)]
+enum LinkKind {
+    Hard,
+    Sym,
+}
+
+fn replace_with_link(keeper: &Path, target: &Path, kind: LinkKind) -> Result<(), FsError> {
+    let parent = target.parent().unwrap_or_else(|| Path::new("."));
+    let tmp = parent.join(".fs_watcher_tmp_link");
+    // Clean up any stale temp link from a previous interrupted run.
+    let _ = fs::remove_file(&tmp);
+
+    match kind {
+        LinkKind::Hard => fs::hard_link(keeper, &tmp)?,
+        LinkKind::Sym => make_symlink(keeper, &tmp)?,
+    }
+
+    // Atomic rename over the original so a crash never loses data.
+    if let Err(e) = fs::rename(&tmp, target) {
+        let _ = fs::remove_file(&tmp);
+        return Err(FsError::Copy(format!(
+            "failed to replace {:?} with link: {}",
+            target, e
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_symlink(src: &Path, dst: &Path) -> Result<(), FsError> {
+    std::os::unix::fs::symlink(src, dst).map_err(FsError::from)
+}
+
+#[cfg(windows)]
+fn make_symlink(src: &Path, dst: &Path) -> Result<(), FsError> {
+    std::os::windows::fs::symlink_file(src, dst).map_err(FsError::from)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn make_symlink(_src: &Path, _dst: &Path) -> Result<(), FsError> {
+    Err(FsError::Copy("symlinks are not supported on this platform".to_string()))
+}
+
+#[cfg(unix)]
+fn shares_inode(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(ma), Ok(mb)) => ma.dev() == mb.dev() && ma.ino() == mb.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn shares_inode(_a: &Path, _b: &Path) -> bool {
+    false
+}
+
+/// Choose the index of the file to keep within a group.
+fn select_keeper(paths: &[String], keep: &str) -> usize {
+    match keep {
+        "newest" | "oldest" => {
+            let want_newest = keep == "newest";
+            paths
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, p)| {
+                    let mtime = fs::metadata(p)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                        .map(|d| d.as_nanos())
+                        .unwrap_or(0);
+                    if want_newest {
+                        mtime
+                    } else {
+                        u128::MAX - mtime
+                    }
+                })
+                .map(|(i, _)| i)
+                .unwrap_or(0)
+        }
+        "shortest_path" => paths
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, p)| p.len())
+            .map(|(i, _)| i)
+            .unwrap_or(0),
+        // Explicit reference path or prefix: prefer a file at/under it.
+        reference => paths
+            .iter()
+            .position(|p| path_at_or_under(p, reference))
+            .unwrap_or(0),
+    }
+}
+
+/// Whether `path` is `reference` itself or lives beneath it, comparing on path
+/// components so a raw string prefix (`/keep` vs `/keepsake`) cannot match
+/// across a non-boundary.
+fn path_at_or_under(path: &str, reference: &str) -> bool {
+    let path = Path::new(path);
+    let reference = Path::new(reference);
+    path == reference || path.starts_with(reference)
+}
+
 fn report_progress(
     py: Python<'_>,
     callback: &Option<PyObject>,
@@ -163,14 +745,20 @@ fn collect_files(
     paths: &[String],
     recursive: bool,
     min_size: u64,
+    max_size: Option<u64>,
+    filter: &CollectFilter,
 ) -> Result<Vec<(PathBuf, u64)>, FsError> {
     let mut entries = Vec::new();
+    let in_window = |size: u64| size >= min_size && max_size.map(|m| size <= m).unwrap_or(true);
 
     for path_str in paths {
         let path = PathBuf::from(path_str);
         if path.is_file() {
+            if !filter.allows_file(&path) {
+                continue;
+            }
             let size = fs::metadata(&path)?.len();
-            if size >= min_size {
+            if in_window(size) {
                 entries.push((path, size));
             }
         } else if path.is_dir() {
@@ -179,13 +767,27 @@ fn collect_files(
                 walkdir = walkdir.max_depth(1);
             }
 
-            for entry in walkdir {
-                if let Ok(entry) = entry {
-                    if entry.file_type().is_file() {
-                        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
-                        if size >= min_size {
-                            entries.push((entry.path().to_path_buf(), size));
-                        }
+            // Prune excluded directories so their subtrees are never read.
+            let excluded_dirs = filter.exclude_dirs.clone();
+            walkdir = walkdir.process_read_dir(move |_, _, _, children| {
+                children.retain(|child| match child {
+                    Ok(entry) if entry.file_type().is_dir() => {
+                        let name = entry.file_name().to_string_lossy();
+                        !excluded_dirs.iter().any(|d| d == name.as_ref())
+                    }
+                    _ => true,
+                });
+            });
+
+            for entry in walkdir.into_iter().flatten() {
+                if entry.file_type().is_file() {
+                    let entry_path = entry.path();
+                    if !filter.allows_file(&entry_path) {
+                        continue;
+                    }
+                    let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                    if in_window(size) {
+                        entries.push((entry_path, size));
                     }
                 }
             }
@@ -239,23 +841,38 @@ fn partial_hash_stage(
 fn full_hash_stage(
     partial_groups: &[(String, u64, Vec<PathBuf>)],
     algo: Algorithm,
+    mut cache: Option<&mut DuplicateCache>,
 ) -> Vec<(String, u64, Vec<PathBuf>)> {
     let mut results: Vec<(String, u64, Vec<PathBuf>)> = Vec::new();
 
     for (_partial_hash, size, files) in partial_groups {
-        // Full-hash all files in this group in parallel
-        let hashes: Vec<(PathBuf, Option<String>)> = files
+        // Full-hash all files in this group in parallel, reusing cached hashes
+        // for files whose size and mtime have not changed since the last run.
+        let hashes: Vec<(PathBuf, Option<fs::Metadata>, Option<String>)> = files
             .par_iter()
             .map(|path| {
-                let result = hash::hash_file_internal(path, algo, 1_048_576).ok();
-                (path.clone(), result.map(|r| r.hash_hex))
+                let meta = fs::metadata(path).ok();
+                let cached = match (&cache, &meta) {
+                    (Some(c), Some(m)) => c.get(*size, path, m, algo),
+                    _ => None,
+                };
+                let hash = match cached {
+                    Some(h) => Some(h),
+                    None => hash::hash_file_internal(path, algo, 1_048_576)
+                        .ok()
+                        .map(|r| r.hash_hex),
+                };
+                (path.clone(), meta, hash)
             })
             .collect();
 
-        // Group by full hash
+        // Group by full hash (and refresh the cache sequentially).
         let mut hash_groups: HashMap<String, Vec<PathBuf>> = HashMap::new();
-        for (path, hash) in hashes {
+        for (path, meta, hash) in hashes {
             if let Some(h) = hash {
+                if let (Some(c), Some(m)) = (cache.as_deref_mut(), &meta) {
+                    c.insert(*size, &path, m, algo, h.clone());
+                }
                 hash_groups.entry(h).or_default().push(path);
             }
         }
@@ -297,11 +914,46 @@ mod tests {
         fs::write(tmp.path().join("b.txt"), "world").unwrap();
         fs::write(tmp.path().join("tiny"), "").unwrap(); // empty
 
-        let entries =
-            collect_files(&[tmp.path().to_string_lossy().into_owned()], true, 1).unwrap();
+        let entries = collect_files(
+            &[tmp.path().to_string_lossy().into_owned()],
+            true,
+            1,
+            None,
+            &CollectFilter::default(),
+        )
+        .unwrap();
         assert_eq!(entries.len(), 2); // empty file filtered by min_size=1
     }
 
+    #[test]
+    fn test_collect_files_extension_and_dir_filter() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("skip")).unwrap();
+        fs::write(tmp.path().join("a.txt"), "hello").unwrap();
+        fs::write(tmp.path().join("b.log"), "world").unwrap();
+        fs::write(tmp.path().join("skip/c.txt"), "nope").unwrap();
+
+        let filter = CollectFilter::build(
+            Some(vec!["txt".to_string()]),
+            None,
+            None,
+            Some(vec!["skip".to_string()]),
+        )
+        .unwrap();
+
+        let entries = collect_files(
+            &[tmp.path().to_string_lossy().into_owned()],
+            true,
+            1,
+            None,
+            &filter,
+        )
+        .unwrap();
+        // Only a.txt: b.log excluded by extension, skip/ pruned entirely.
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].0.ends_with("a.txt"));
+    }
+
     #[test]
     fn test_full_pipeline() {
         let tmp = TempDir::new().unwrap();
@@ -316,7 +968,7 @@ mod tests {
         fs::write(tmp.path().join("unique.bin"), &[2u8; 5000]).unwrap();
 
         let path_str = tmp.path().to_string_lossy().into_owned();
-        let entries = collect_files(&[path_str], true, 1).unwrap();
+        let entries = collect_files(&[path_str], true, 1, None, &CollectFilter::default()).unwrap();
         let size_groups: Vec<(u64, Vec<PathBuf>)> = group_by_size(entries)
             .into_iter()
             .filter(|(_, files)| files.len() > 1)
@@ -334,8 +986,148 @@ mod tests {
         let full = full_hash_stage(
             &partial.iter().filter(|(_, _, f)| f.len() > 1).cloned().collect::<Vec<_>>(),
             Algorithm::Blake3,
+            None,
         );
         let full_dup: Vec<_> = full.iter().filter(|(_, _, f)| f.len() > 1).collect();
         assert_eq!(full_dup.len(), 2);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_inode_breakdown_collapses_hardlinks() {
+        let tmp = TempDir::new().unwrap();
+        let a = tmp.path().join("a.bin");
+        let b = tmp.path().join("b.bin");
+        fs::write(&a, vec![3u8; 256]).unwrap();
+        fs::hard_link(&a, &b).unwrap();
+
+        let paths = vec![
+            a.to_string_lossy().into_owned(),
+            b.to_string_lossy().into_owned(),
+        ];
+        let (real, hardlinked) = inode_breakdown(&paths);
+        assert_eq!(real, 1);
+        assert_eq!(hardlinked.len(), 1);
+    }
+
+    #[test]
+    fn test_largest_bounded_top_n() {
+        let entries = vec![
+            (PathBuf::from("/a"), 10),
+            (PathBuf::from("/b"), 50),
+            (PathBuf::from("/c"), 30),
+            (PathBuf::from("/d"), 40),
+        ];
+        let top = largest(entries, Some(2));
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].file_size, 50);
+        assert_eq!(top[1].file_size, 40);
+    }
+
+    #[test]
+    fn test_largest_all() {
+        let entries = vec![(PathBuf::from("/a"), 10), (PathBuf::from("/b"), 20)];
+        let all = largest(entries, None);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].file_size, 20);
+    }
+
+    #[test]
+    fn test_select_keeper_shortest_path() {
+        let paths = vec![
+            "/a/deep/nested/file.bin".to_string(),
+            "/a/file.bin".to_string(),
+        ];
+        assert_eq!(select_keeper(&paths, "shortest_path"), 1);
+    }
+
+    #[test]
+    fn test_select_keeper_reference_prefix() {
+        let paths = vec![
+            "/tmp/copy/file.bin".to_string(),
+            "/keep/file.bin".to_string(),
+        ];
+        assert_eq!(select_keeper(&paths, "/keep"), 1);
+    }
+
+    #[test]
+    fn test_resolve_delete() {
+        let tmp = TempDir::new().unwrap();
+        let a = tmp.path().join("a.bin");
+        let b = tmp.path().join("b.bin");
+        fs::write(&a, vec![9u8; 1000]).unwrap();
+        fs::write(&b, vec![9u8; 1000]).unwrap();
+
+        let group = DuplicateGroup {
+            hash_hex: "x".to_string(),
+            file_size: 1000,
+            paths: vec![
+                a.to_string_lossy().into_owned(),
+                b.to_string_lossy().into_owned(),
+            ],
+            real_copies: 2,
+            hardlinked_paths: Vec::new(),
+        };
+
+        let summary = resolve_groups(&[group], ResolveAction::Delete, "shortest_path").unwrap();
+        assert_eq!(summary.bytes_reclaimed, 1000);
+        // Exactly one of the two files remains on disk.
+        assert_ne!(a.exists(), b.exists());
+    }
+
+    #[test]
+    fn test_resolve_dry_run_touches_nothing() {
+        let tmp = TempDir::new().unwrap();
+        let a = tmp.path().join("a.bin");
+        let b = tmp.path().join("b.bin");
+        fs::write(&a, vec![1u8; 500]).unwrap();
+        fs::write(&b, vec![1u8; 500]).unwrap();
+
+        let group = DuplicateGroup {
+            hash_hex: "x".to_string(),
+            file_size: 500,
+            paths: vec![
+                a.to_string_lossy().into_owned(),
+                b.to_string_lossy().into_owned(),
+            ],
+            real_copies: 2,
+            hardlinked_paths: Vec::new(),
+        };
+
+        let summary = resolve_groups(&[group], ResolveAction::DryRun, "newest").unwrap();
+        assert_eq!(summary.bytes_reclaimed, 500);
+        assert!(a.exists() && b.exists());
+    }
+
+    #[test]
+    fn test_duplicate_cache_roundtrip() {
+        let tmp = TempDir::new().unwrap();
+        let file = tmp.path().join("a.bin");
+        fs::write(&file, vec![7u8; 8192]).unwrap();
+        let meta = fs::metadata(&file).unwrap();
+
+        let mut cache = DuplicateCache::default();
+        assert!(cache.get(8192, &file, &meta, Algorithm::Blake3).is_none());
+        cache.insert(8192, &file, &meta, Algorithm::Blake3, "deadbeef".to_string());
+        assert_eq!(
+            cache.get(8192, &file, &meta, Algorithm::Blake3).as_deref(),
+            Some("deadbeef")
+        );
+        // A different algorithm must not reuse the entry.
+        assert!(cache.get(8192, &file, &meta, Algorithm::Sha256).is_none());
+
+        let cache_file = tmp.path().join("cache.json");
+        cache.save(&cache_file).unwrap();
+        let loaded = DuplicateCache::load(&cache_file);
+        assert_eq!(
+            loaded.get(8192, &file, &meta, Algorithm::Blake3).as_deref(),
+            Some("deadbeef")
+        );
+
+        // Pruning drops entries whose path has vanished.
+        let mut loaded = loaded;
+        fs::remove_file(&file).unwrap();
+        loaded.prune();
+        assert!(loaded.entries.is_empty());
+    }
 }
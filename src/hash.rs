@@ -52,6 +52,8 @@ impl HashResult {
 pub enum Algorithm {
     Sha256,
     Blake3,
+    Xxh3,
+    Crc32,
 }
 
 impl Algorithm {
@@ -59,8 +61,10 @@ impl Algorithm {
         match s {
             "sha256" => Ok(Algorithm::Sha256),
             "blake3" => Ok(Algorithm::Blake3),
+            "xxh3" => Ok(Algorithm::Xxh3),
+            "crc32" => Ok(Algorithm::Crc32),
             other => Err(FsError::Hash(format!(
-                "unknown algorithm {:?}, expected \"sha256\" or \"blake3\"",
+                "unknown algorithm {:?}, expected \"sha256\", \"blake3\", \"xxh3\" or \"crc32\"",
                 other
             ))),
         }
@@ -70,8 +74,64 @@ impl Algorithm {
         match self {
             Algorithm::Sha256 => "sha256",
             Algorithm::Blake3 => "blake3",
+            Algorithm::Xxh3 => "xxh3",
+            Algorithm::Crc32 => "crc32",
         }
     }
+
+    /// Construct a streaming hasher for this algorithm. All read paths (mmap
+    /// and buffered) feed bytes through the resulting [`FsHasher`], so there is
+    /// a single `match` over the algorithm rather than one per read path.
+    pub fn hasher(&self) -> Box<dyn FsHasher> {
+        match self {
+            Algorithm::Sha256 => Box::new(sha2::Sha256::new()),
+            Algorithm::Blake3 => Box::new(blake3::Hasher::new()),
+            Algorithm::Xxh3 => Box::new(xxhash_rust::xxh3::Xxh3::new()),
+            Algorithm::Crc32 => Box::new(crc32fast::Hasher::new()),
+        }
+    }
+}
+
+/// A streaming hasher backend producing a lowercase-hex digest.
+pub trait FsHasher {
+    fn update(&mut self, bytes: &[u8]);
+    fn finalize(self: Box<Self>) -> String;
+}
+
+impl FsHasher for sha2::Sha256 {
+    fn update(&mut self, bytes: &[u8]) {
+        Digest::update(self, bytes);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        format!("{:x}", Digest::finalize(*self))
+    }
+}
+
+impl FsHasher for blake3::Hasher {
+    fn update(&mut self, bytes: &[u8]) {
+        blake3::Hasher::update(self, bytes);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        blake3::Hasher::finalize(&self).to_hex().to_string()
+    }
+}
+
+impl FsHasher for xxhash_rust::xxh3::Xxh3 {
+    fn update(&mut self, bytes: &[u8]) {
+        xxhash_rust::xxh3::Xxh3::update(self, bytes);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        format!("{:016x}", self.digest())
+    }
+}
+
+impl FsHasher for crc32fast::Hasher {
+    fn update(&mut self, bytes: &[u8]) {
+        crc32fast::Hasher::update(self, bytes);
+    }
+    fn finalize(self: Box<Self>) -> String {
+        format!("{:08x}", crc32fast::Hasher::finalize(*self))
+    }
 }
 
 /// Hash a single file.
@@ -186,48 +246,292 @@ pub fn hash_file_internal(
 }
 
 fn hash_bytes(data: &[u8], algorithm: Algorithm) -> String {
-    match algorithm {
-        Algorithm::Sha256 => {
-            let mut hasher = sha2::Sha256::new();
-            hasher.update(data);
-            format!("{:x}", hasher.finalize())
-        }
-        Algorithm::Blake3 => {
-            let hash = blake3::hash(data);
-            hash.to_hex().to_string()
-        }
-    }
+    let mut hasher = algorithm.hasher();
+    hasher.update(data);
+    hasher.finalize()
 }
 
 fn hash_buffered(path: &Path, algorithm: Algorithm, chunk_size: usize) -> Result<String, FsError> {
     let file = File::open(path)?;
     let mut reader = BufReader::with_capacity(chunk_size, file);
     let mut buf = vec![0u8; chunk_size];
+    let mut hasher = algorithm.hasher();
 
-    match algorithm {
-        Algorithm::Sha256 => {
-            let mut hasher = sha2::Sha256::new();
-            loop {
-                let n = reader.read(&mut buf)?;
-                if n == 0 {
-                    break;
-                }
-                hasher.update(&buf[..n]);
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize())
+}
+
+/// An ordered list of per-block BLAKE3 hashes plus a root digest computed over
+/// their concatenation — a simple Merkle manifest for incremental
+/// re-verification of large, append-mostly files.
+#[pyclass(frozen)]
+#[derive(Clone)]
+pub struct ChunkManifest {
+    #[pyo3(get)]
+    pub root_hex: String,
+    #[pyo3(get)]
+    pub block_size: usize,
+    #[pyo3(get)]
+    pub blocks: Vec<String>,
+}
+
+#[pymethods]
+impl ChunkManifest {
+    fn __repr__(&self) -> String {
+        format!(
+            "ChunkManifest(root={}, {} blocks x {}B)",
+            &self.root_hex[..16.min(self.root_hex.len())],
+            self.blocks.len(),
+            self.block_size
+        )
+    }
+
+    fn __len__(&self) -> usize {
+        self.blocks.len()
+    }
+}
+
+/// Build a [`ChunkManifest`] of per-block BLAKE3 hashes for a file.
+///
+/// The file is split into fixed `block_size` blocks (the final block may be
+/// shorter); each block is hashed with BLAKE3 and the root is the BLAKE3 of the
+/// concatenated block digests. A later [`verify_against_manifest`] can re-hash
+/// only the blocks overlapping a changed byte range, avoiding a full re-read.
+#[pyfunction]
+#[pyo3(signature = (path, *, block_size=1_048_576))]
+pub fn hash_file_tree(py: Python<'_>, path: &str, block_size: usize) -> PyResult<ChunkManifest> {
+    if block_size == 0 {
+        return Err(FsError::Hash("block_size must be greater than zero".to_string()).into());
+    }
+    let file_path = PathBuf::from(path);
+    let manifest = py.allow_threads(|| build_manifest(&file_path, block_size))?;
+    Ok(manifest)
+}
+
+fn build_manifest(path: &Path, block_size: usize) -> Result<ChunkManifest, FsError> {
+    let file = File::open(path)?;
+    let metadata = file.metadata()?;
+    let file_size = metadata.len();
+
+    let mmap;
+    let owned;
+    let data: &[u8] = if file_size > MMAP_THRESHOLD {
+        mmap = mmap_file(&file)?;
+        &mmap
+    } else {
+        owned = fs::read(path)?;
+        &owned
+    };
+
+    let mut blocks = Vec::new();
+    let mut root = blake3::Hasher::new();
+    for block in data.chunks(block_size) {
+        let hash = blake3::hash(block);
+        root.update(hash.as_bytes());
+        blocks.push(hash.to_hex().to_string());
+    }
+
+    Ok(ChunkManifest {
+        root_hex: root.finalize().to_hex().to_string(),
+        block_size,
+        blocks,
+    })
+}
+
+/// Re-verify a file against a previously built [`ChunkManifest`], re-hashing
+/// only the blocks that overlap one of the supplied changed byte ranges
+/// (`(offset, length)` pairs, e.g. derived from a `FileWatcher`).
+///
+/// Returns the indices of blocks whose current hash no longer matches the
+/// manifest. Blocks that the file no longer contains, or new blocks past the
+/// manifest's end, are reported as mismatched.
+#[pyfunction]
+#[pyo3(signature = (path, manifest, changed_ranges))]
+pub fn verify_against_manifest(
+    py: Python<'_>,
+    path: &str,
+    manifest: &ChunkManifest,
+    changed_ranges: Vec<(u64, u64)>,
+) -> PyResult<Vec<usize>> {
+    let file_path = PathBuf::from(path);
+    let block_size = manifest.block_size;
+    if block_size == 0 {
+        return Err(FsError::Hash("manifest block_size must be greater than zero".to_string()).into());
+    }
+
+    let mismatched = py.allow_threads(|| -> Result<Vec<usize>, FsError> {
+        // Collect the block indices touched by the changed ranges.
+        let mut indices: Vec<usize> = Vec::new();
+        for (offset, length) in &changed_ranges {
+            if *length == 0 {
+                continue;
             }
-            Ok(format!("{:x}", hasher.finalize()))
+            let first = (*offset / block_size as u64) as usize;
+            let last = ((*offset + *length - 1) / block_size as u64) as usize;
+            indices.extend(first..=last);
         }
-        Algorithm::Blake3 => {
-            let mut hasher = blake3::Hasher::new();
-            loop {
-                let n = reader.read(&mut buf)?;
-                if n == 0 {
-                    break;
-                }
-                hasher.update(&buf[..n]);
+        indices.sort_unstable();
+        indices.dedup();
+
+        let mut file = File::open(&file_path)?;
+        let file_size = file.metadata()?.len();
+        let mut buf = vec![0u8; block_size];
+        let mut mismatched = Vec::new();
+
+        for idx in indices {
+            let start = idx as u64 * block_size as u64;
+            if idx >= manifest.blocks.len() || start >= file_size {
+                // New block past the manifest, or block no longer present.
+                mismatched.push(idx);
+                continue;
             }
-            Ok(hasher.finalize().to_hex().to_string())
+            let avail = (file_size - start).min(block_size as u64) as usize;
+            file.seek(SeekFrom::Start(start))?;
+            file.read_exact(&mut buf[..avail])?;
+            let hash = blake3::hash(&buf[..avail]).to_hex().to_string();
+            if hash != manifest.blocks[idx] {
+                mismatched.push(idx);
+            }
+        }
+
+        Ok(mismatched)
+    })?;
+
+    Ok(mismatched)
+}
+
+/// Precomputed gear table of pseudo-random 64-bit constants (splitmix64).
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        table[i] = z;
+        i += 1;
+    }
+    table
+}
+
+/// Split a file into variable-length content-defined chunks and return
+/// `[(offset, length, blake3_hex)]` for each chunk.
+///
+/// Uses FastCDC-style gear chunking with normalized chunking: a stricter mask
+/// (more 1-bits) is applied while the current chunk is shorter than `avg_size`,
+/// and a looser mask once past it, with `min_size`/`max_size` enforced as hard
+/// floors/ceilings regardless of the rolling hash. Large files are read through
+/// the shared mmap path. Because content boundaries follow the data, inserting
+/// or deleting bytes only reshuffles the chunks near the edit, letting callers
+/// detect shared regions across files and versions.
+#[pyfunction]
+#[pyo3(signature = (path, *, avg_size=65536, min_size=None, max_size=None))]
+pub fn chunk_file(
+    py: Python<'_>,
+    path: &str,
+    avg_size: usize,
+    min_size: Option<usize>,
+    max_size: Option<usize>,
+) -> PyResult<Vec<(u64, u64, String)>> {
+    let file_path = PathBuf::from(path);
+    let min_size = min_size.unwrap_or(avg_size / 4).max(1);
+    let max_size = max_size.unwrap_or(avg_size * 4).max(min_size);
+
+    let chunks = py.allow_threads(|| chunk_file_internal(&file_path, avg_size, min_size, max_size))?;
+    Ok(chunks)
+}
+
+fn chunk_file_internal(
+    path: &Path,
+    avg_size: usize,
+    min_size: usize,
+    max_size: usize,
+) -> Result<Vec<(u64, u64, String)>, FsError> {
+    let metadata = fs::metadata(path)?;
+    let file_size = metadata.len();
+
+    // Read the whole file; use mmap for large files, a buffered read otherwise.
+    let file = File::open(path)?;
+    let mmap;
+    let owned;
+    let data: &[u8] = if file_size > MMAP_THRESHOLD {
+        mmap = mmap_file(&file)?;
+        &mmap
+    } else {
+        owned = fs::read(path)?;
+        &owned
+    };
+
+    // Normalized chunking masks: mask_s has more 1-bits (harder to cut, used
+    // while the chunk is still short), mask_l fewer (easier to cut afterwards).
+    let bits = if avg_size <= 1 {
+        0
+    } else {
+        63 - (avg_size as u64).leading_zeros()
+    };
+    let mask_s = ((1u64 << (bits + 1).min(63)) - 1) as u64;
+    let mask_l = (1u64 << bits.saturating_sub(1)).wrapping_sub(1);
+
+    let mut chunks = Vec::new();
+    let mut offset = 0usize;
+    while offset < data.len() {
+        let len = cut_point(&data[offset..], min_size, avg_size, max_size, mask_s, mask_l);
+        let chunk = &data[offset..offset + len];
+        let hex = blake3::hash(chunk).to_hex().to_string();
+        chunks.push((offset as u64, len as u64, hex));
+        offset += len;
+    }
+
+    Ok(chunks)
+}
+
+/// Find the cut point for the next chunk within `data`.
+fn cut_point(
+    data: &[u8],
+    min_size: usize,
+    avg_size: usize,
+    max_size: usize,
+    mask_s: u64,
+    mask_l: u64,
+) -> usize {
+    let len = data.len();
+    if len <= min_size {
+        return len;
+    }
+    let hard_max = max_size.min(len);
+    let normal = avg_size.min(hard_max);
+
+    let mut fp = 0u64;
+    let mut i = min_size;
+
+    // Stricter mask while the chunk is shorter than the average target.
+    while i < normal {
+        fp = (fp << 1).wrapping_add(GEAR[data[i] as usize]);
+        if fp & mask_s == 0 {
+            return i;
+        }
+        i += 1;
+    }
+    // Looser mask up to the hard maximum.
+    while i < hard_max {
+        fp = (fp << 1).wrapping_add(GEAR[data[i] as usize]);
+        if fp & mask_l == 0 {
+            return i;
         }
+        i += 1;
     }
+    hard_max
 }
 
 /// Hash the first and last `size` bytes of a file (for dedup partial hashing).
@@ -252,20 +556,10 @@ pub fn partial_hash(path: &Path, algorithm: Algorithm, size: usize) -> Result<St
     file.read_exact(&mut tail)?;
 
     // Hash the concatenation of head + tail
-    match algorithm {
-        Algorithm::Sha256 => {
-            let mut hasher = sha2::Sha256::new();
-            hasher.update(&head);
-            hasher.update(&tail);
-            Ok(format!("{:x}", hasher.finalize()))
-        }
-        Algorithm::Blake3 => {
-            let mut hasher = blake3::Hasher::new();
-            hasher.update(&head);
-            hasher.update(&tail);
-            Ok(hasher.finalize().to_hex().to_string())
-        }
-    }
+    let mut hasher = algorithm.hasher();
+    hasher.update(&head);
+    hasher.update(&tail);
+    Ok(hasher.finalize())
 }
 
 #[cfg(test)]
@@ -301,6 +595,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_xxh3_and_crc32() {
+        let mut f = NamedTempFile::new().unwrap();
+        f.write_all(b"hello world").unwrap();
+        f.flush().unwrap();
+
+        let xxh3 = hash_file_internal(f.path(), Algorithm::Xxh3, 1024).unwrap();
+        assert_eq!(xxh3.algorithm, "xxh3");
+        assert_eq!(xxh3.hash_hex.len(), 16);
+
+        let crc32 = hash_file_internal(f.path(), Algorithm::Crc32, 1024).unwrap();
+        assert_eq!(crc32.algorithm, "crc32");
+        assert_eq!(crc32.hash_hex.len(), 8);
+        // crc32 of "hello world" is a well-known constant.
+        assert_eq!(crc32.hash_hex, "0d4a1185");
+    }
+
     #[test]
     fn test_empty_file() {
         let f = NamedTempFile::new().unwrap();
@@ -320,6 +631,59 @@ mod tests {
         assert_eq!(partial, full.hash_hex);
     }
 
+    #[test]
+    fn test_chunk_file_covers_whole_file() {
+        let mut f = NamedTempFile::new().unwrap();
+        // Pseudo-random-ish content so cut points actually trigger.
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i.wrapping_mul(2654435761) >> 13) as u8).collect();
+        f.write_all(&data).unwrap();
+        f.flush().unwrap();
+
+        let chunks = chunk_file_internal(f.path(), 4096, 1024, 16384).unwrap();
+        assert!(chunks.len() > 1);
+
+        // Chunks are contiguous and cover the whole file.
+        let mut expected_offset = 0u64;
+        for (offset, len, hex) in &chunks {
+            assert_eq!(*offset, expected_offset);
+            assert!(*len >= 1024 || *offset + *len == data.len() as u64);
+            assert!(*len <= 16384);
+            assert_eq!(hex.len(), 64);
+            expected_offset += len;
+        }
+        assert_eq!(expected_offset, data.len() as u64);
+    }
+
+    #[test]
+    fn test_manifest_build_and_rebuild() {
+        let mut f = NamedTempFile::new().unwrap();
+        let data = vec![0u8; 10_000];
+        f.write_all(&data).unwrap();
+        f.flush().unwrap();
+
+        let manifest = build_manifest(f.path(), 4096).unwrap();
+        // 10_000 bytes / 4096 -> 3 blocks.
+        assert_eq!(manifest.blocks.len(), 3);
+        assert_eq!(manifest.block_size, 4096);
+        assert!(!manifest.root_hex.is_empty());
+
+        // Rebuilding an unchanged file yields the same root.
+        let again = build_manifest(f.path(), 4096).unwrap();
+        assert_eq!(manifest.root_hex, again.root_hex);
+    }
+
+    #[test]
+    fn test_chunk_file_deterministic() {
+        let mut f = NamedTempFile::new().unwrap();
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i.wrapping_mul(40503) >> 7) as u8).collect();
+        f.write_all(&data).unwrap();
+        f.flush().unwrap();
+
+        let a = chunk_file_internal(f.path(), 4096, 1024, 16384).unwrap();
+        let b = chunk_file_internal(f.path(), 4096, 1024, 16384).unwrap();
+        assert_eq!(a, b);
+    }
+
     #[test]
     fn test_partial_hash_large_file() {
         let mut f = NamedTempFile::new().unwrap();
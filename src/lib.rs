@@ -6,6 +6,7 @@ mod copy;
 mod dedup;
 mod errors;
 mod hash;
+mod snapshot;
 mod utils;
 mod walk;
 mod watch;
@@ -31,8 +32,12 @@ fn _core(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // Hash
     m.add_class::<hash::HashResult>()?;
+    m.add_class::<hash::ChunkManifest>()?;
     m.add_function(wrap_pyfunction!(hash::hash_file, m)?)?;
     m.add_function(wrap_pyfunction!(hash::hash_files, m)?)?;
+    m.add_function(wrap_pyfunction!(hash::chunk_file, m)?)?;
+    m.add_function(wrap_pyfunction!(hash::hash_file_tree, m)?)?;
+    m.add_function(wrap_pyfunction!(hash::verify_against_manifest, m)?)?;
 
     // Copy/Move
     m.add_class::<copy::CopyProgress>()?;
@@ -45,7 +50,16 @@ fn _core(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // Dedup
     m.add_class::<dedup::DuplicateGroup>()?;
+    m.add_class::<dedup::ResolveSummary>()?;
+    m.add_class::<dedup::LargeFile>()?;
     m.add_function(wrap_pyfunction!(dedup::find_duplicates, m)?)?;
+    m.add_function(wrap_pyfunction!(dedup::resolve_duplicates, m)?)?;
+    m.add_function(wrap_pyfunction!(dedup::find_large_files, m)?)?;
+
+    // Snapshot
+    m.add_class::<snapshot::ChangeRecord>()?;
+    m.add_function(wrap_pyfunction!(snapshot::write_snapshot, m)?)?;
+    m.add_function(wrap_pyfunction!(snapshot::diff_snapshot, m)?)?;
 
     Ok(())
 }
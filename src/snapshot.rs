@@ -0,0 +1,363 @@
+//! Persistent directory snapshots with fast diffing.
+//!
+//! A snapshot captures `(relative_path, size, mtime_ns, inode, is_dir)` for
+//! every entry in a tree and serializes it into a compact binary blob: a small
+//! header ("docket") holding a format version and the total data length,
+//! followed by a sorted sequence of fixed-layout records. This mirrors
+//! Mercurial's dirstate-v2 layout and lets callers diff the current tree
+//! against a stored snapshot without rescanning file contents.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use pyo3::prelude::*;
+
+use crate::errors::FsError;
+
+const MAGIC: &[u8; 4] = b"FSW1";
+const FORMAT_VERSION: u16 = 1;
+/// Header size: magic (4) + version (2) + data length (8).
+const HEADER_LEN: usize = 4 + 2 + 8;
+
+/// A single change reported by [`diff_snapshot`].
+#[pyclass(frozen)]
+#[derive(Clone)]
+pub struct ChangeRecord {
+    /// One of `"added"`, `"removed"`, `"modified"`, `"moved"`.
+    #[pyo3(get)]
+    pub change_type: String,
+    #[pyo3(get)]
+    pub path: String,
+    /// For `"moved"` entries, the path the entry moved from; `None` otherwise.
+    #[pyo3(get)]
+    pub old_path: Option<String>,
+    #[pyo3(get)]
+    pub is_dir: bool,
+}
+
+#[pymethods]
+impl ChangeRecord {
+    fn __repr__(&self) -> String {
+        match &self.old_path {
+            Some(old) => format!("ChangeRecord({}, {:?} <- {:?})", self.change_type, self.path, old),
+            None => format!("ChangeRecord({}, {:?})", self.change_type, self.path),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Entry {
+    relpath: String,
+    size: u64,
+    mtime_ns: u64,
+    inode: u64,
+    is_dir: bool,
+}
+
+/// Capture a snapshot of `path` and write it to `out_file`.
+///
+/// Returns the number of entries recorded.
+#[pyfunction]
+pub fn write_snapshot(py: Python<'_>, path: &str, out_file: &str) -> PyResult<usize> {
+    let root = PathBuf::from(path);
+    let out = PathBuf::from(out_file);
+    let count = py.allow_threads(|| -> Result<usize, FsError> {
+        let entries = collect_entries(&root)?;
+        let blob = serialize(&entries);
+        fs::write(&out, blob)?;
+        Ok(entries.len())
+    })?;
+    Ok(count)
+}
+
+/// Diff the current state of `path` against a previously written snapshot.
+#[pyfunction]
+pub fn diff_snapshot(
+    py: Python<'_>,
+    path: &str,
+    snapshot_file: &str,
+) -> PyResult<Vec<ChangeRecord>> {
+    let root = PathBuf::from(path);
+    let snapshot = PathBuf::from(snapshot_file);
+    let changes = py.allow_threads(|| -> Result<Vec<ChangeRecord>, FsError> {
+        let old_bytes = fs::read(&snapshot)?;
+        let old = deserialize(&old_bytes)?;
+        let current = collect_entries(&root)?;
+        Ok(diff(old, current))
+    })?;
+    Ok(changes)
+}
+
+fn collect_entries(root: &Path) -> Result<Vec<Entry>, FsError> {
+    if !root.is_dir() {
+        return Err(FsError::Walk(format!("path is not a directory: {:?}", root)));
+    }
+
+    let mut entries = Vec::new();
+    for entry in jwalk::WalkDir::new(root).into_iter().flatten() {
+        if entry.depth == 0 {
+            continue;
+        }
+        let path = entry.path();
+        let rel = match path.strip_prefix(root) {
+            Ok(r) => r.to_string_lossy().into_owned(),
+            Err(_) => continue,
+        };
+        let ft = entry.file_type();
+        let meta = entry.metadata().ok();
+        let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+        let mtime_ns = meta
+            .as_ref()
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        let inode = inode_of(meta.as_ref());
+
+        entries.push(Entry {
+            relpath: rel,
+            size,
+            mtime_ns,
+            inode,
+            is_dir: ft.is_dir(),
+        });
+    }
+
+    // Sort for a stable, newline-free on-disk tree.
+    entries.sort_by(|a, b| a.relpath.cmp(&b.relpath));
+    Ok(entries)
+}
+
+#[cfg(unix)]
+fn inode_of(meta: Option<&fs::Metadata>) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.map(|m| m.ino()).unwrap_or(0)
+}
+
+#[cfg(not(unix))]
+fn inode_of(_meta: Option<&fs::Metadata>) -> u64 {
+    0
+}
+
+fn serialize(entries: &[Entry]) -> Vec<u8> {
+    let mut data = Vec::new();
+    for e in entries {
+        data.extend_from_slice(&e.size.to_le_bytes());
+        data.extend_from_slice(&e.mtime_ns.to_le_bytes());
+        data.extend_from_slice(&e.inode.to_le_bytes());
+        data.push(e.is_dir as u8);
+        let path_bytes = e.relpath.as_bytes();
+        let len = path_bytes.len().min(u16::MAX as usize) as u16;
+        data.extend_from_slice(&len.to_le_bytes());
+        data.extend_from_slice(&path_bytes[..len as usize]);
+    }
+
+    let mut out = Vec::with_capacity(HEADER_LEN + data.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    out.extend_from_slice(&data);
+    out
+}
+
+fn deserialize(bytes: &[u8]) -> Result<Vec<Entry>, FsError> {
+    if bytes.len() < HEADER_LEN {
+        return Err(FsError::Walk("snapshot file too small".to_string()));
+    }
+    if &bytes[..4] != MAGIC {
+        return Err(FsError::Walk("invalid snapshot magic".to_string()));
+    }
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version != FORMAT_VERSION {
+        return Err(FsError::Walk(format!(
+            "unsupported snapshot version {}, expected {}",
+            version, FORMAT_VERSION
+        )));
+    }
+    let data_len = u64::from_le_bytes(bytes[6..14].try_into().unwrap()) as usize;
+    let data = &bytes[HEADER_LEN..];
+    if data.len() != data_len {
+        return Err(FsError::Walk(format!(
+            "snapshot truncated or corrupt: header declares {} data bytes, found {}",
+            data_len,
+            data.len()
+        )));
+    }
+
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        if pos + 25 > data.len() {
+            return Err(FsError::Walk("snapshot record truncated".to_string()));
+        }
+        let size = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+        let mtime_ns = u64::from_le_bytes(data[pos + 8..pos + 16].try_into().unwrap());
+        let inode = u64::from_le_bytes(data[pos + 16..pos + 24].try_into().unwrap());
+        let is_dir = data[pos + 24] != 0;
+        pos += 25;
+        let plen = u16::from_le_bytes(data[pos..pos + 2].try_into().unwrap()) as usize;
+        pos += 2;
+        if pos + plen > data.len() {
+            return Err(FsError::Walk("snapshot path truncated".to_string()));
+        }
+        let relpath = String::from_utf8_lossy(&data[pos..pos + plen]).into_owned();
+        pos += plen;
+        entries.push(Entry {
+            relpath,
+            size,
+            mtime_ns,
+            inode,
+            is_dir,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn diff(old: Vec<Entry>, current: Vec<Entry>) -> Vec<ChangeRecord> {
+    let old_map: HashMap<String, Entry> =
+        old.into_iter().map(|e| (e.relpath.clone(), e)).collect();
+    let current_map: HashMap<String, Entry> = current
+        .iter()
+        .map(|e| (e.relpath.clone(), e.clone()))
+        .collect();
+
+    let mut changes = Vec::new();
+    let mut added: Vec<Entry> = Vec::new();
+    let mut removed: Vec<Entry> = Vec::new();
+
+    for e in &current {
+        match old_map.get(&e.relpath) {
+            Some(prev) => {
+                if prev.size != e.size || prev.mtime_ns != e.mtime_ns {
+                    changes.push(ChangeRecord {
+                        change_type: "modified".to_string(),
+                        path: e.relpath.clone(),
+                        old_path: None,
+                        is_dir: e.is_dir,
+                    });
+                }
+            }
+            None => added.push(e.clone()),
+        }
+    }
+    for (path, e) in &old_map {
+        if !current_map.contains_key(path) {
+            removed.push(e.clone());
+        }
+    }
+
+    // Correlate add/remove pairs sharing an inode into "moved" events.
+    let mut consumed = vec![false; removed.len()];
+    for a in &added {
+        let matched = if a.inode != 0 {
+            removed
+                .iter()
+                .enumerate()
+                .position(|(i, r)| !consumed[i] && r.inode == a.inode)
+        } else {
+            None
+        };
+        match matched {
+            Some(i) => {
+                consumed[i] = true;
+                changes.push(ChangeRecord {
+                    change_type: "moved".to_string(),
+                    path: a.relpath.clone(),
+                    old_path: Some(removed[i].relpath.clone()),
+                    is_dir: a.is_dir,
+                });
+            }
+            None => changes.push(ChangeRecord {
+                change_type: "added".to_string(),
+                path: a.relpath.clone(),
+                old_path: None,
+                is_dir: a.is_dir,
+            }),
+        }
+    }
+    for (i, r) in removed.iter().enumerate() {
+        if consumed[i] {
+            continue;
+        }
+        changes.push(ChangeRecord {
+            change_type: "removed".to_string(),
+            path: r.relpath.clone(),
+            old_path: None,
+            is_dir: r.is_dir,
+        });
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_serialize_roundtrip() {
+        let entries = vec![
+            Entry {
+                relpath: "a.txt".to_string(),
+                size: 10,
+                mtime_ns: 12345,
+                inode: 7,
+                is_dir: false,
+            },
+            Entry {
+                relpath: "sub".to_string(),
+                size: 0,
+                mtime_ns: 0,
+                inode: 8,
+                is_dir: true,
+            },
+        ];
+        let blob = serialize(&entries);
+        let back = deserialize(&blob).unwrap();
+        assert_eq!(back.len(), 2);
+        assert_eq!(back[0].relpath, "a.txt");
+        assert_eq!(back[0].size, 10);
+        assert!(back[1].is_dir);
+    }
+
+    #[test]
+    fn test_deserialize_truncated_errors() {
+        let entries = vec![Entry {
+            relpath: "a.txt".to_string(),
+            size: 10,
+            mtime_ns: 1,
+            inode: 1,
+            is_dir: false,
+        }];
+        let mut blob = serialize(&entries);
+        blob.truncate(blob.len() - 3);
+        assert!(deserialize(&blob).is_err());
+    }
+
+    #[test]
+    fn test_diff_detects_changes() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join("keep.txt"), "same").unwrap();
+        fs::write(root.join("gone.txt"), "bye").unwrap();
+
+        let old = collect_entries(root).unwrap();
+
+        // Create before removing so new.txt cannot reuse gone.txt's inode,
+        // which diff() would report as a move.
+        fs::write(root.join("new.txt"), "hello").unwrap();
+        fs::remove_file(root.join("gone.txt")).unwrap();
+
+        let current = collect_entries(root).unwrap();
+        let changes = diff(old, current);
+
+        let added = changes.iter().filter(|c| c.change_type == "added").count();
+        let removed = changes.iter().filter(|c| c.change_type == "removed").count();
+        assert_eq!(added, 1);
+        assert_eq!(removed, 1);
+    }
+}
@@ -1,8 +1,10 @@
-use std::path::PathBuf;
-use std::sync::mpsc;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 
 use globset::{Glob, GlobMatcher};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use jwalk::WalkDir;
 use pyo3::prelude::*;
 
@@ -82,10 +84,22 @@ impl WalkIter {
 struct WalkOptions {
     max_depth: Option<usize>,
     follow_symlinks: bool,
-    sort: bool,
+    sort: SortMode,
     skip_hidden: bool,
     file_type: FileTypeFilter,
     glob_matcher: Option<GlobMatcher>,
+    respect_gitignore: bool,
+}
+
+/// Ordering applied to sibling entries during traversal.
+#[derive(Clone, Copy, PartialEq)]
+enum SortMode {
+    /// No ordering (jwalk returns entries in readdir order).
+    Off,
+    /// jwalk's built-in lexicographic (byte-wise) ordering.
+    Lexicographic,
+    /// Natural/numeric ordering, so `file2` sorts before `file10`.
+    Natural,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -99,17 +113,18 @@ enum FileTypeFilter {
 ///
 /// Uses jwalk for parallel directory reading, significantly faster than os.walk().
 #[pyfunction]
-#[pyo3(signature = (path, *, max_depth=None, follow_symlinks=false, sort=false, skip_hidden=false, file_type="any", glob_pattern=None))]
+#[pyo3(signature = (path, *, max_depth=None, follow_symlinks=false, sort=None, skip_hidden=false, file_type="any", glob_pattern=None, respect_gitignore=false))]
 #[allow(clippy::too_many_arguments)]
 pub fn walk(
     py: Python<'_>,
     path: &str,
     max_depth: Option<usize>,
     follow_symlinks: bool,
-    sort: bool,
+    sort: Option<Bound<'_, PyAny>>,
     skip_hidden: bool,
     file_type: &str,
     glob_pattern: Option<&str>,
+    respect_gitignore: bool,
 ) -> PyResult<WalkIter> {
     let root = PathBuf::from(path);
     if !root.exists() {
@@ -119,6 +134,7 @@ pub fn walk(
         return Err(FsError::Walk(format!("path is not a directory: {}", path)).into());
     }
 
+    let sort = parse_sort_mode(sort.as_ref())?;
     let opts = parse_walk_options(
         max_depth,
         follow_symlinks,
@@ -126,6 +142,7 @@ pub fn walk(
         skip_hidden,
         file_type,
         glob_pattern,
+        respect_gitignore,
     )?;
     let (sender, receiver) = mpsc::channel();
 
@@ -147,17 +164,18 @@ pub fn walk(
 /// Faster than walk() when you need all entries, because it avoids per-item
 /// GIL overhead by running the entire traversal in Rust.
 #[pyfunction]
-#[pyo3(signature = (path, *, max_depth=None, follow_symlinks=false, sort=false, skip_hidden=false, file_type="any", glob_pattern=None))]
+#[pyo3(signature = (path, *, max_depth=None, follow_symlinks=false, sort=None, skip_hidden=false, file_type="any", glob_pattern=None, respect_gitignore=false))]
 #[allow(clippy::too_many_arguments)]
 pub fn walk_collect(
     py: Python<'_>,
     path: &str,
     max_depth: Option<usize>,
     follow_symlinks: bool,
-    sort: bool,
+    sort: Option<Bound<'_, PyAny>>,
     skip_hidden: bool,
     file_type: &str,
     glob_pattern: Option<&str>,
+    respect_gitignore: bool,
 ) -> PyResult<Vec<WalkEntry>> {
     let root = PathBuf::from(path);
     if !root.exists() {
@@ -167,6 +185,7 @@ pub fn walk_collect(
         return Err(FsError::Walk(format!("path is not a directory: {}", path)).into());
     }
 
+    let sort = parse_sort_mode(sort.as_ref())?;
     let opts = parse_walk_options(
         max_depth,
         follow_symlinks,
@@ -174,6 +193,7 @@ pub fn walk_collect(
         skip_hidden,
         file_type,
         glob_pattern,
+        respect_gitignore,
     )?;
 
     let results = py.allow_threads(|| collect_walk(root, opts));
@@ -181,13 +201,87 @@ pub fn walk_collect(
     Ok(results)
 }
 
+/// Interpret the Python `sort` argument, which may be `True`/`False` or the
+/// string `"natural"`.
+fn parse_sort_mode(sort: Option<&Bound<'_, PyAny>>) -> PyResult<SortMode> {
+    let obj = match sort {
+        Some(o) if !o.is_none() => o,
+        _ => return Ok(SortMode::Off),
+    };
+    if let Ok(s) = obj.extract::<String>() {
+        return match s.as_str() {
+            "natural" => Ok(SortMode::Natural),
+            other => Err(FsError::Walk(format!(
+                "invalid sort: {:?} (expected True, False, or \"natural\")",
+                other
+            ))
+            .into()),
+        };
+    }
+    match obj.extract::<bool>() {
+        Ok(true) => Ok(SortMode::Lexicographic),
+        Ok(false) => Ok(SortMode::Off),
+        Err(_) => Err(FsError::Walk("sort must be a bool or \"natural\"".to_string()).into()),
+    }
+}
+
+/// Compare two names by natural/numeric ordering: maximal digit runs compare by
+/// value (ignoring leading zeros, longer run wins on a numeric tie), everything
+/// else byte-wise.
+fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    fn trim_zeros(s: &[u8]) -> &[u8] {
+        let mut k = 0;
+        while k + 1 < s.len() && s[k] == b'0' {
+            k += 1;
+        }
+        &s[k..]
+    }
+
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0usize, 0usize);
+    while i < a.len() && j < b.len() {
+        if a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            let si = i;
+            while i < a.len() && a[i].is_ascii_digit() {
+                i += 1;
+            }
+            let sj = j;
+            while j < b.len() && b[j].is_ascii_digit() {
+                j += 1;
+            }
+            let na = trim_zeros(&a[si..i]);
+            let nb = trim_zeros(&b[sj..j]);
+            let ord = na.len().cmp(&nb.len()).then_with(|| na.cmp(nb));
+            if ord != Ordering::Equal {
+                return ord;
+            }
+            // Equal value: the run with more (leading-zero) digits sorts later.
+            let ord = (i - si).cmp(&(j - sj));
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        } else {
+            let ord = a[i].cmp(&b[j]);
+            if ord != Ordering::Equal {
+                return ord;
+            }
+            i += 1;
+            j += 1;
+        }
+    }
+    (a.len() - i).cmp(&(b.len() - j))
+}
+
 fn parse_walk_options(
     max_depth: Option<usize>,
     follow_symlinks: bool,
-    sort: bool,
+    sort: SortMode,
     skip_hidden: bool,
     file_type: &str,
     glob_pattern: Option<&str>,
+    respect_gitignore: bool,
 ) -> PyResult<WalkOptions> {
     let file_type = match file_type {
         "any" => FileTypeFilter::Any,
@@ -218,6 +312,7 @@ fn parse_walk_options(
         skip_hidden,
         file_type,
         glob_matcher,
+        respect_gitignore,
     })
 }
 
@@ -276,13 +371,150 @@ fn build_walkdir(root: PathBuf, opts: &WalkOptions) -> WalkDir {
         walkdir = walkdir.max_depth(depth);
     }
 
-    if opts.sort {
+    if opts.sort == SortMode::Lexicographic {
         walkdir = walkdir.sort(true);
     }
 
+    // Natural ordering and gitignore pruning both run inside the per-directory
+    // read hook (jwalk allows only one), so combine them into a single closure.
+    let natural = opts.sort == SortMode::Natural;
+    if opts.respect_gitignore || natural {
+        let ignore_root = root.clone();
+        let respect = opts.respect_gitignore;
+        let cache = IgnoreCache::new();
+        walkdir = walkdir.process_read_dir(move |_depth, dir_path, _state, children| {
+            if respect {
+                // Assemble the ordered stack of `.gitignore` / `.ignore`
+                // matchers from the root down; the deepest matching rule wins
+                // (negations re-include). Per-directory matchers are compiled
+                // once and reused from `cache`, so a deep walk does not re-read
+                // the ancestor chain for every directory entered.
+                let stack = cache.stack_for(&ignore_root, dir_path);
+                if !stack.is_empty() {
+                    children.retain(|child| match child {
+                        Ok(entry) => {
+                            let path = entry.path();
+                            let is_dir = entry.file_type().is_dir();
+                            !is_path_ignored(&stack, &path, is_dir)
+                        }
+                        Err(_) => true,
+                    });
+                }
+            }
+            if natural {
+                children.sort_by(|a, b| match (a, b) {
+                    (Ok(x), Ok(y)) => natural_cmp(
+                        &x.file_name().to_string_lossy(),
+                        &y.file_name().to_string_lossy(),
+                    ),
+                    _ => std::cmp::Ordering::Equal,
+                });
+            }
+        });
+    }
+
     walkdir
 }
 
+/// Memoizes each directory's own compiled ignore matcher so a deep walk
+/// compiles every `.gitignore` / `.ignore` at most once, instead of re-reading
+/// the full ancestor chain each time a directory is entered.
+#[derive(Clone)]
+struct IgnoreCache {
+    /// Global git excludes (lowest priority), compiled once; `None` if empty.
+    global: Option<Gitignore>,
+    /// Per-directory matcher for that directory's own ignore files; the stored
+    /// `None` records a directory that has no (non-empty) matcher.
+    per_dir: Arc<Mutex<HashMap<PathBuf, Option<Gitignore>>>>,
+}
+
+impl IgnoreCache {
+    fn new() -> Self {
+        let (global, _) = Gitignore::global();
+        let global = if global.num_ignores() > 0 || global.num_whitelists() > 0 {
+            Some(global)
+        } else {
+            None
+        };
+        IgnoreCache {
+            global,
+            per_dir: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The compiled matcher for a single directory's own ignore files, or
+    /// `None` if it has none. Compiled on first request and cached thereafter.
+    fn matcher_for(&self, dir: &Path) -> Option<Gitignore> {
+        if let Ok(map) = self.per_dir.lock() {
+            if let Some(hit) = map.get(dir) {
+                return hit.clone();
+            }
+        }
+
+        let mut builder = GitignoreBuilder::new(dir);
+        let gitignore = dir.join(".gitignore");
+        if gitignore.is_file() {
+            builder.add(&gitignore);
+        }
+        let ignore = dir.join(".ignore");
+        if ignore.is_file() {
+            builder.add(&ignore);
+        }
+        let compiled = builder
+            .build()
+            .ok()
+            .filter(|gi| gi.num_ignores() > 0 || gi.num_whitelists() > 0);
+
+        if let Ok(mut map) = self.per_dir.lock() {
+            map.insert(dir.to_path_buf(), compiled.clone());
+        }
+        compiled
+    }
+
+    /// Build the ordered stack of ignore matchers from `root` down to `dir`
+    /// (shallowest first), plus any global git excludes at the base, reusing
+    /// compiled per-directory matchers from the cache.
+    fn stack_for(&self, root: &Path, dir: &Path) -> Vec<Gitignore> {
+        // Collect root -> dir inclusive.
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        let mut cur = Some(dir);
+        while let Some(c) = cur {
+            dirs.push(c.to_path_buf());
+            if c == root {
+                break;
+            }
+            cur = c.parent();
+        }
+        dirs.reverse();
+
+        let mut stack = Vec::new();
+        if let Some(ref global) = self.global {
+            stack.push(global.clone());
+        }
+        for d in dirs {
+            if let Some(gi) = self.matcher_for(&d) {
+                stack.push(gi);
+            }
+        }
+        stack
+    }
+}
+
+/// Evaluate the ignore stack for `path`. The deepest matcher wins; within a
+/// matcher the `ignore` crate already gives later lines precedence.
+fn is_path_ignored(stack: &[Gitignore], path: &Path, is_dir: bool) -> bool {
+    for gi in stack.iter().rev() {
+        let m = gi.matched(path, is_dir);
+        if m.is_ignore() {
+            return true;
+        }
+        if m.is_whitelist() {
+            return false;
+        }
+    }
+    false
+}
+
 fn run_walk(root: PathBuf, opts: WalkOptions, sender: mpsc::Sender<Result<WalkEntry, String>>) {
     let walkdir = build_walkdir(root, &opts);
 
@@ -354,10 +586,11 @@ mod tests {
         let opts = WalkOptions {
             max_depth: None,
             follow_symlinks: false,
-            sort: true,
+            sort: SortMode::Lexicographic,
             skip_hidden: false,
             file_type: FileTypeFilter::Any,
             glob_matcher: None,
+            respect_gitignore: false,
         };
 
         let results = collect_walk(tmp.path().to_path_buf(), opts);
@@ -371,10 +604,11 @@ mod tests {
         let opts = WalkOptions {
             max_depth: None,
             follow_symlinks: false,
-            sort: true,
+            sort: SortMode::Lexicographic,
             skip_hidden: false,
             file_type: FileTypeFilter::File,
             glob_matcher: None,
+            respect_gitignore: false,
         };
 
         let results = collect_walk(tmp.path().to_path_buf(), opts);
@@ -388,10 +622,11 @@ mod tests {
         let opts = WalkOptions {
             max_depth: None,
             follow_symlinks: false,
-            sort: true,
+            sort: SortMode::Lexicographic,
             skip_hidden: true,
             file_type: FileTypeFilter::File,
             glob_matcher: None,
+            respect_gitignore: false,
         };
 
         let results = collect_walk(tmp.path().to_path_buf(), opts);
@@ -413,10 +648,11 @@ mod tests {
         let opts = WalkOptions {
             max_depth: None,
             follow_symlinks: false,
-            sort: true,
+            sort: SortMode::Lexicographic,
             skip_hidden: false,
             file_type: FileTypeFilter::File,
             glob_matcher: Some(glob),
+            respect_gitignore: false,
         };
 
         let results = collect_walk(tmp.path().to_path_buf(), opts);
@@ -424,16 +660,100 @@ mod tests {
         assert_eq!(results.len(), 3); // file1.txt, file3.txt, top.txt
     }
 
+    #[test]
+    fn test_respect_gitignore() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::create_dir_all(root.join("build")).unwrap();
+        fs::write(root.join(".gitignore"), "*.log\nbuild/\n!keep.log\n").unwrap();
+        fs::write(root.join("main.rs"), "code").unwrap();
+        fs::write(root.join("debug.log"), "noise").unwrap();
+        fs::write(root.join("keep.log"), "wanted").unwrap();
+        fs::write(root.join("build/out.bin"), "artifact").unwrap();
+
+        let opts = WalkOptions {
+            max_depth: None,
+            follow_symlinks: false,
+            sort: SortMode::Lexicographic,
+            skip_hidden: false,
+            file_type: FileTypeFilter::File,
+            glob_matcher: None,
+            respect_gitignore: true,
+        };
+
+        let results = collect_walk(root.to_path_buf(), opts);
+        let names: Vec<String> = results
+            .iter()
+            .map(|e| {
+                std::path::Path::new(&e.path)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+
+        assert!(names.contains(&"main.rs".to_string()));
+        assert!(names.contains(&"keep.log".to_string())); // re-included by negation
+        assert!(!names.contains(&"debug.log".to_string()));
+        assert!(!names.contains(&"out.bin".to_string())); // build/ pruned
+    }
+
+    #[test]
+    fn test_natural_cmp_orders_numerically() {
+        use std::cmp::Ordering;
+        assert_eq!(natural_cmp("file2.txt", "file10.txt"), Ordering::Less);
+        assert_eq!(natural_cmp("file10.txt", "file2.txt"), Ordering::Greater);
+        assert_eq!(natural_cmp("a", "a"), Ordering::Equal);
+        assert_eq!(natural_cmp("img9", "img009"), Ordering::Less); // fewer leading zeros first
+        assert_eq!(natural_cmp("v1.2", "v1.10"), Ordering::Less);
+
+        let mut names = vec!["file10", "file2", "file1"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(names, vec!["file1", "file2", "file10"]);
+    }
+
+    #[test]
+    fn test_natural_sort_mode() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        for name in ["f1", "f2", "f10", "f20"] {
+            fs::write(root.join(name), "x").unwrap();
+        }
+        let opts = WalkOptions {
+            max_depth: None,
+            follow_symlinks: false,
+            sort: SortMode::Natural,
+            skip_hidden: false,
+            file_type: FileTypeFilter::File,
+            glob_matcher: None,
+            respect_gitignore: false,
+        };
+        let results = collect_walk(root.to_path_buf(), opts);
+        let names: Vec<String> = results
+            .iter()
+            .map(|e| {
+                Path::new(&e.path)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(names, vec!["f1", "f2", "f10", "f20"]);
+    }
+
     #[test]
     fn test_max_depth() {
         let tmp = create_test_tree();
         let opts = WalkOptions {
             max_depth: Some(1),
             follow_symlinks: false,
-            sort: true,
+            sort: SortMode::Lexicographic,
             skip_hidden: false,
             file_type: FileTypeFilter::Any,
             glob_matcher: None,
+            respect_gitignore: false,
         };
 
         let results = collect_walk(tmp.path().to_path_buf(), opts);
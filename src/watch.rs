@@ -1,16 +1,20 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use crossbeam_channel as channel;
 
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use notify_debouncer_full::notify::{RecursiveMode, Watcher};
-use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, FileIdMap};
+use notify_debouncer_full::{
+    new_debouncer, DebounceEventResult, DebouncedEvent, Debouncer, FileIdCache, FileIdMap,
+};
 use pyo3::prelude::*;
 
 use crate::errors::FsError;
+use crate::hash::{self, Algorithm};
 
 // Re-export the notify types through the debouncer's version to avoid version conflicts
 use notify_debouncer_full::notify;
@@ -23,6 +27,9 @@ pub struct FileChange {
     pub path: String,
     #[pyo3(get)]
     pub change_type: String,
+    /// For `"moved"` events, the path the file was moved from; `None` otherwise.
+    #[pyo3(get)]
+    pub old_path: Option<String>,
     #[pyo3(get)]
     pub is_dir: bool,
     #[pyo3(get)]
@@ -33,10 +40,16 @@ pub struct FileChange {
 impl FileChange {
     fn __repr__(&self) -> String {
         let kind = if self.is_dir { "dir" } else { "file" };
-        format!(
-            "FileChange({:?}, {}, {})",
-            self.path, self.change_type, kind
-        )
+        match &self.old_path {
+            Some(old) => format!(
+                "FileChange({:?} -> {:?}, {}, {})",
+                old, self.path, self.change_type, kind
+            ),
+            None => format!(
+                "FileChange({:?}, {}, {})",
+                self.path, self.change_type, kind
+            ),
+        }
     }
 }
 
@@ -47,6 +60,8 @@ pub struct FileWatcher {
     recursive: bool,
     debounce_ms: u64,
     ignore_glob_set: Option<GlobSet>,
+    detect_content_changes: bool,
+    content_hashes: Mutex<HashMap<PathBuf, (String, String)>>,
     receiver: Option<channel::Receiver<DebounceEventResult>>,
     debouncer: Option<Debouncer<notify::RecommendedWatcher, FileIdMap>>,
     running: Arc<AtomicBool>,
@@ -55,12 +70,13 @@ pub struct FileWatcher {
 #[pymethods]
 impl FileWatcher {
     #[new]
-    #[pyo3(signature = (path, *, recursive=true, debounce_ms=500, ignore_patterns=None))]
+    #[pyo3(signature = (path, *, recursive=true, debounce_ms=500, ignore_patterns=None, detect_content_changes=false))]
     fn new(
         path: &str,
         recursive: bool,
         debounce_ms: u64,
         ignore_patterns: Option<Vec<String>>,
+        detect_content_changes: bool,
     ) -> PyResult<Self> {
         let watch_path = PathBuf::from(path);
         if !watch_path.exists() {
@@ -89,6 +105,8 @@ impl FileWatcher {
             recursive,
             debounce_ms,
             ignore_glob_set,
+            detect_content_changes,
+            content_hashes: Mutex::new(HashMap::new()),
             receiver: None,
             debouncer: None,
             running: Arc::new(AtomicBool::new(false)),
@@ -125,6 +143,10 @@ impl FileWatcher {
 
         debouncer.cache().add_root(&self.path, mode);
 
+        if self.detect_content_changes {
+            self.prime_content_hashes();
+        }
+
         self.debouncer = Some(debouncer);
         self.receiver = Some(receiver);
         self.running.store(true, Ordering::SeqCst);
@@ -158,35 +180,7 @@ impl FileWatcher {
                     .unwrap_or_default()
                     .as_secs_f64();
 
-                let mut changes = Vec::new();
-                for event in events {
-                    let change_type = match event.kind {
-                        notify::EventKind::Create(_) => "created",
-                        notify::EventKind::Modify(_) => "modified",
-                        notify::EventKind::Remove(_) => "deleted",
-                        _ => continue,
-                    };
-
-                    for path in &event.paths {
-                        // Apply ignore patterns
-                        if let Some(ref glob_set) = self.ignore_glob_set {
-                            if let Some(name) = path.file_name() {
-                                if glob_set.is_match(name) {
-                                    continue;
-                                }
-                            }
-                        }
-
-                        changes.push(FileChange {
-                            path: path.to_string_lossy().into_owned(),
-                            change_type: change_type.to_string(),
-                            is_dir: path.is_dir(),
-                            timestamp: now,
-                        });
-                    }
-                }
-
-                Ok(changes)
+                Ok(self.events_to_changes(events, now))
             }
             Ok(Err(errors)) => {
                 for e in &errors {
@@ -233,6 +227,236 @@ impl FileWatcher {
     }
 }
 
+impl FileWatcher {
+    /// Whether `path` is filtered out by the configured ignore patterns.
+    fn is_ignored(&self, path: &std::path::Path) -> bool {
+        if let Some(ref glob_set) = self.ignore_glob_set {
+            if let Some(name) = path.file_name() {
+                return glob_set.is_match(name);
+            }
+        }
+        false
+    }
+
+    /// Translate a batch of debounced notify events into `FileChange`s,
+    /// collapsing rename/move pairs into a single `"moved"` event.
+    ///
+    /// Renames surface as `"moved"` in three ways: a platform-provided
+    /// `Modify(Name(Both))` event carrying both paths, a `From`/`To` pair, or a
+    /// bare remove+create pair that the debouncer's `FileIdMap` reports as the
+    /// same file id (inode) within the debounce window.
+    fn events_to_changes(&self, events: Vec<DebouncedEvent>, now: f64) -> Vec<FileChange> {
+        use notify::event::{ModifyKind, RenameMode};
+
+        let mut changes = Vec::new();
+        let mut pending_creates: Vec<PathBuf> = Vec::new();
+        let mut pending_removes: Vec<PathBuf> = Vec::new();
+
+        for event in &events {
+            match event.kind {
+                notify::EventKind::Modify(ModifyKind::Name(RenameMode::Both))
+                    if event.paths.len() >= 2 =>
+                {
+                    self.push_move(&mut changes, &event.paths[0], &event.paths[1], now);
+                }
+                notify::EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                    pending_removes.extend(event.paths.iter().cloned());
+                }
+                notify::EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                    pending_creates.extend(event.paths.iter().cloned());
+                }
+                notify::EventKind::Create(_) => {
+                    pending_creates.extend(event.paths.iter().cloned());
+                }
+                notify::EventKind::Remove(_) => {
+                    pending_removes.extend(event.paths.iter().cloned());
+                }
+                notify::EventKind::Modify(_) => {
+                    for path in &event.paths {
+                        if self.is_ignored(path) {
+                            continue;
+                        }
+                        // Suppress metadata-only "modified" events whose content
+                        // digest is unchanged from the last time we saw the file.
+                        if self.detect_content_changes
+                            && path.is_file()
+                            && !self.content_changed(path)
+                        {
+                            continue;
+                        }
+                        changes.push(FileChange {
+                            path: path.to_string_lossy().into_owned(),
+                            change_type: "modified".to_string(),
+                            old_path: None,
+                            is_dir: path.is_dir(),
+                            timestamp: now,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Correlate remaining remove/create pairs that share a file id.
+        let cache = self.debouncer.as_ref().map(|d| d.cache());
+        let mut consumed = vec![false; pending_creates.len()];
+        for rem in &pending_removes {
+            let rem_id = cache.and_then(|c| c.cached_file_id(rem).cloned());
+            let matched = rem_id.as_ref().and_then(|rid| {
+                pending_creates.iter().enumerate().position(|(i, cre)| {
+                    !consumed[i]
+                        && cache.and_then(|c| c.cached_file_id(cre).cloned()).as_ref() == Some(rid)
+                })
+            });
+            match matched {
+                Some(i) => {
+                    consumed[i] = true;
+                    self.push_move(&mut changes, rem, &pending_creates[i], now);
+                }
+                None => {
+                    if self.is_ignored(rem) {
+                        continue;
+                    }
+                    changes.push(FileChange {
+                        path: rem.to_string_lossy().into_owned(),
+                        change_type: "deleted".to_string(),
+                        old_path: None,
+                        is_dir: false,
+                        timestamp: now,
+                    });
+                }
+            }
+        }
+        for (i, cre) in pending_creates.iter().enumerate() {
+            if consumed[i] || self.is_ignored(cre) {
+                continue;
+            }
+            changes.push(FileChange {
+                path: cre.to_string_lossy().into_owned(),
+                change_type: "created".to_string(),
+                old_path: None,
+                is_dir: cre.is_dir(),
+                timestamp: now,
+            });
+        }
+
+        changes
+    }
+
+    /// Hash every existing file under the watch root into `content_hashes`
+    /// before the first event is delivered.
+    ///
+    /// Without this, a pre-existing file's first metadata-only touch hits the
+    /// `None` arm of `content_changed` and is forwarded as a spurious change.
+    /// Priming the cache up front lets that first touch be suppressed, which is
+    /// the primary reason content detection exists.
+    fn prime_content_hashes(&self) {
+        let algo = Algorithm::Xxh3;
+        let mut walkdir = jwalk::WalkDir::new(&self.path).follow_links(false);
+        if !self.recursive {
+            walkdir = walkdir.max_depth(1);
+        }
+
+        let mut cache = match self.content_hashes.lock() {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        for entry in walkdir.into_iter().flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path = entry.path();
+            if self.is_ignored(&path) {
+                continue;
+            }
+            let partial = match hash::partial_hash(&path, algo, 4096) {
+                Ok(p) => p,
+                Err(_) => continue,
+            };
+            let full = match hash::hash_file_internal(&path, algo, 1_048_576) {
+                Ok(r) => r.hash_hex,
+                Err(_) => continue,
+            };
+            cache.insert(path, (partial, full));
+        }
+    }
+
+    /// Whether a file's content has changed since we last hashed it, updating
+    /// the per-path digest cache as a side effect.
+    ///
+    /// Compares a cheap head+tail partial digest first: a differing partial
+    /// digest reports a change, and a colliding one is confirmed against the
+    /// cached full digest. The full digest is computed on every call that
+    /// reaches the cache (first sight, differing partial, or collision), so
+    /// each such call reads the whole file. An unhashable file is
+    /// conservatively treated as changed.
+    fn content_changed(&self, path: &Path) -> bool {
+        let algo = Algorithm::Xxh3;
+        let partial = match hash::partial_hash(path, algo, 4096) {
+            Ok(p) => p,
+            Err(_) => return true,
+        };
+
+        let mut cache = match self.content_hashes.lock() {
+            Ok(c) => c,
+            Err(_) => return true,
+        };
+
+        match cache.get(path) {
+            // Partial digest differs: the file definitely changed. Still read
+            // the full digest so `cached_full` stays fresh for a future
+            // partial-digest collision.
+            Some((cached_partial, _)) if *cached_partial != partial => {
+                let full = hash::hash_file_internal(path, algo, 1_048_576)
+                    .map(|r| r.hash_hex)
+                    .unwrap_or_default();
+                cache.insert(path.to_path_buf(), (partial, full));
+                true
+            }
+            // Partial collision: confirm with a full digest.
+            Some((_, cached_full)) => {
+                let full = match hash::hash_file_internal(path, algo, 1_048_576) {
+                    Ok(r) => r.hash_hex,
+                    Err(_) => return true,
+                };
+                if *cached_full == full {
+                    false
+                } else {
+                    cache.insert(path.to_path_buf(), (partial, full));
+                    true
+                }
+            }
+            // First time we see this file: record it and report as changed.
+            None => {
+                let full = hash::hash_file_internal(path, algo, 1_048_576)
+                    .map(|r| r.hash_hex)
+                    .unwrap_or_default();
+                cache.insert(path.to_path_buf(), (partial, full));
+                true
+            }
+        }
+    }
+
+    fn push_move(
+        &self,
+        changes: &mut Vec<FileChange>,
+        old: &std::path::Path,
+        new: &std::path::Path,
+        now: f64,
+    ) {
+        if self.is_ignored(new) {
+            return;
+        }
+        changes.push(FileChange {
+            path: new.to_string_lossy().into_owned(),
+            change_type: "moved".to_string(),
+            old_path: Some(old.to_string_lossy().into_owned()),
+            is_dir: new.is_dir(),
+            timestamp: now,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,4 +472,44 @@ mod tests {
         assert!(set.is_match("error.log"));
         assert!(!set.is_match("data.txt"));
     }
+
+    #[test]
+    fn test_moved_repr_shows_from_to() {
+        let change = FileChange {
+            path: "/new/b.txt".to_string(),
+            change_type: "moved".to_string(),
+            old_path: Some("/old/a.txt".to_string()),
+            is_dir: false,
+            timestamp: 0.0,
+        };
+        assert!(change.__repr__().contains("->"));
+    }
+
+    #[test]
+    fn test_content_change_detection() {
+        use std::io::Write;
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file = tmp.path().join("a.txt");
+        std::fs::write(&file, b"hello").unwrap();
+
+        let watcher = FileWatcher::new(
+            tmp.path().to_str().unwrap(),
+            true,
+            500,
+            None,
+            true,
+        )
+        .unwrap();
+
+        // First sight: reported as changed and cached.
+        assert!(watcher.content_changed(&file));
+        // Same content: suppressed.
+        assert!(!watcher.content_changed(&file));
+
+        // Real edit: reported as changed again.
+        let mut f = std::fs::OpenOptions::new().write(true).open(&file).unwrap();
+        f.write_all(b"world!!").unwrap();
+        f.flush().unwrap();
+        assert!(watcher.content_changed(&file));
+    }
 }